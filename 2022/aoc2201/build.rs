@@ -0,0 +1,7 @@
+use anyhow::Result;
+
+use aoc_input::download;
+
+fn main() -> Result<()> {
+    download(2022, 1)
+}
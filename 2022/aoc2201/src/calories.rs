@@ -0,0 +1,179 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use anyhow::{bail, Context, Result};
+
+/// The calories carried by each elf, in the order they appear in the input.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Inventory {
+    totals: Vec<usize>,
+}
+
+impl Inventory {
+    /// Parses one item per line, with elves separated only by blank lines.
+    /// Any other line that is not a number is an error.
+    pub fn parse(s: &str) -> Result<Inventory> {
+        let mut totals = vec![];
+        let mut current: Option<usize> = None;
+
+        for (idx, line) in s.lines().enumerate() {
+            let value = line.trim();
+            if value.is_empty() {
+                totals.extend(current.take());
+                continue;
+            }
+
+            let column = line.chars().take_while(|c| c.is_whitespace()).count() + 1;
+            let Ok(calories) = value.parse::<usize>() else {
+                bail!(
+                    "line {}, column {column}: invalid calories {value:?}",
+                    idx + 1
+                )
+            };
+
+            let total = current.unwrap_or_default().checked_add(calories);
+            current =
+                Some(total.with_context(|| format!("line {}: elf total overflows", idx + 1))?);
+        }
+        totals.extend(current);
+
+        Ok(Inventory { totals })
+    }
+
+    pub fn totals(&self) -> &[usize] {
+        &self.totals
+    }
+
+    /// The index of the elf carrying the most calories, taking the first on
+    /// a tie.
+    pub fn richest(&self) -> Option<usize> {
+        self.rankings().first().map(|(idx, _)| *idx)
+    }
+
+    /// Every elf's index and total, from most calories to least, keeping
+    /// input order between equal totals.
+    pub fn rankings(&self) -> Vec<(usize, usize)> {
+        let mut rankings: Vec<(usize, usize)> = self.totals.iter().copied().enumerate().collect();
+        rankings.sort_by_key(|(_, total)| Reverse(*total));
+        rankings
+    }
+
+    /// Sums the calories of the `k` elves carrying the most, keeping only the
+    /// best `k` totals in a min-heap as it goes.
+    pub fn top_k(&self, k: usize) -> Result<usize> {
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+
+        for total in &self.totals {
+            heap.push(Reverse(*total));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+
+        heap.into_iter()
+            .try_fold(0usize, |sum, Reverse(total)| sum.checked_add(total))
+            .with_context(|| format!("sum of the top {k} totals overflows"))
+    }
+}
+
+pub fn top_k(input: &str, k: usize) -> Result<usize> {
+    Inventory::parse(input)?.top_k(k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parse::testing::{is_positioned, mutated};
+    use proptest::prelude::*;
+    use test_case::test_case;
+
+    const INPUT: &str = "\
+    1000
+    2000
+    3000
+
+    4000
+
+    5000
+    6000
+
+    7000
+    8000
+    9000
+
+    10000
+    ";
+
+    #[test]
+    fn test_inventory_totals() -> Result<()> {
+        let inventory = Inventory::parse(INPUT)?;
+        assert_eq!(inventory.totals(), &[6000, 4000, 11000, 24000, 10000]);
+        assert_eq!(inventory.richest(), Some(3));
+        assert_eq!(
+            inventory.rankings(),
+            vec![(3, 24000), (2, 11000), (4, 10000), (0, 6000), (1, 4000)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_inventory_empty() -> Result<()> {
+        let inventory = Inventory::parse("\n\n")?;
+        assert!(inventory.totals().is_empty());
+        assert_eq!(inventory.richest(), None);
+        Ok(())
+    }
+
+    #[test_case("1000\n2x00\n", "line 2, column 1: invalid calories \"2x00\"" ; "corrupt number")]
+    #[test_case("1000\n\n  -5", "line 3, column 3: invalid calories \"-5\"" ; "negative")]
+    #[test_case("1000\n---\n2000", "line 2, column 1: invalid calories \"---\"" ; "not a separator")]
+    #[test_case(
+        "18446744073709551615\n1", "line 2: elf total overflows" ;
+        "overflow"
+    )]
+    fn test_inventory_parse_errors(input: &str, expected: &str) {
+        let err = Inventory::parse(input).expect_err("inventory should not parse");
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[test_case(0, 0 ; "none")]
+    #[test_case(1, 24000 ; "one")]
+    #[test_case(3, 45000 ; "three")]
+    #[test_case(10, 55000 ; "more than elves")]
+    fn test_top_k(k: usize, expected: usize) -> Result<()> {
+        assert_eq!(top_k(INPUT, k)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_top_k_overflow() {
+        let input = format!("{}\n\n{}\n", usize::MAX, 1);
+        let err = top_k(&input, 2).expect_err("sum should overflow");
+        assert_eq!(err.to_string(), "sum of the top 2 totals overflows");
+        assert_eq!(top_k(&input, 1).ok(), Some(usize::MAX));
+    }
+
+    fn inventories() -> impl Strategy<Value = String> {
+        let elf = prop::collection::vec(0..100_000usize, 1..5);
+        prop::collection::vec(elf, 0..10).prop_map(|elves| {
+            elves
+                .iter()
+                .map(|items| items.iter().map(|c| format!("{c}\n")).collect())
+                .collect::<Vec<String>>()
+                .join("\n")
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_inventory_parse_valid(input in inventories()) {
+            prop_assert!(Inventory::parse(&input).is_ok());
+        }
+
+        #[test]
+        fn test_inventory_parse_mutated(input in mutated(inventories())) {
+            if let Err(err) = Inventory::parse(&input) {
+                prop_assert!(is_positioned(err.as_ref()), "{err:#}");
+            }
+        }
+    }
+}
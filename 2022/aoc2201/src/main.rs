@@ -1,9 +1,14 @@
-use aoc2201::*;
+use anyhow::{Context, Result};
+use aoc2201::{one, two};
 
-fn main() {
-    let input = include_str!("../input.txt");
+const INPUT: &str = include_str!("../input");
 
-    let calories = most_calories(input);
+fn main() -> Result<()> {
+    let most = one::most_calories(INPUT).with_context(|| "part one")?;
+    let top_three = two::top_three(INPUT).with_context(|| "part two")?;
 
-    println!("Most calories: {calories}");
+    println!("Most calories: {most}");
+    println!("Top three calories: {top_three}");
+
+    Ok(())
 }
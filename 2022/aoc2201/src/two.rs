@@ -0,0 +1,37 @@
+use anyhow::Result;
+use registry::aoc;
+
+use crate::calories::top_k;
+
+#[aoc(year = 2022, day = 1, part = 2)]
+pub fn top_three(input: &str) -> Result<usize> {
+    top_k(input, 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "\
+    1000
+    2000
+    3000
+
+    4000
+
+    5000
+    6000
+
+    7000
+    8000
+    9000
+
+    10000
+    ";
+
+    #[test]
+    fn test_top_three() -> Result<()> {
+        assert_eq!(top_three(INPUT)?, 45000);
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+use anyhow::{anyhow, Context, Result};
+use parse::{Failure, Failures};
+
+use crate::vocabulary::Vocabulary;
+
+/// How a report treats lines that have no calibration value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Fail with every bad line.
+    #[default]
+    Strict,
+    /// Skip bad lines, keeping them as warnings on the report for the caller
+    /// to show, as `main` does on stderr.
+    Lenient,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Calibration {
+    pub line: usize,
+    pub first: u32,
+    pub last: u32,
+    pub value: u32,
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    calibrations: Vec<Calibration>,
+    warnings: Vec<Failure>,
+}
+
+impl Report {
+    pub fn parse(input: &str, vocabulary: &Vocabulary, mode: Mode) -> Result<Report, Failures> {
+        let mut calibrations = vec![];
+        let mut failures = vec![];
+
+        for (idx, line) in input.lines().enumerate() {
+            match evaluate_line(idx + 1, line, vocabulary) {
+                Ok(calibration) => calibrations.push(calibration),
+                Err(err) => failures.push(Failure::new(idx + 1, err)),
+            }
+        }
+
+        match mode {
+            Mode::Strict if !failures.is_empty() => Err(Failures { failures }),
+            Mode::Strict => Ok(Report {
+                calibrations,
+                warnings: vec![],
+            }),
+            Mode::Lenient => Ok(Report {
+                calibrations,
+                warnings: failures,
+            }),
+        }
+    }
+
+    pub fn calibrations(&self) -> &[Calibration] {
+        &self.calibrations
+    }
+
+    /// Lines skipped in lenient mode, always empty in strict mode.
+    pub fn warnings(&self) -> &[Failure] {
+        &self.warnings
+    }
+
+    pub fn total(&self) -> Result<u32> {
+        self.calibrations
+            .iter()
+            .try_fold(0u32, |sum, calibration| sum.checked_add(calibration.value))
+            .with_context(|| "calibration total overflows u32")
+    }
+}
+
+pub fn calibrate(input: &str, vocabulary: &Vocabulary) -> Result<u32> {
+    Report::parse(input, vocabulary, Mode::Strict)?.total()
+}
+
+pub(crate) fn evaluate_line(line: usize, s: &str, vocabulary: &Vocabulary) -> Result<Calibration> {
+    let numbers = vocabulary.find(s);
+
+    // a line with any digit has both a first and a last one
+    let (Some(&first), Some(&last)) = (numbers.first(), numbers.last()) else {
+        return Err(anyhow!("no digit found"));
+    };
+
+    let value = first
+        .checked_mul(10)
+        .and_then(|tens| tens.checked_add(last))
+        .with_context(|| "calibration value overflows u32")?;
+
+    Ok(Calibration {
+        line,
+        first,
+        last,
+        value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use parse::testing::{is_positioned, mutated};
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const INPUT: &str = "1abc2\nnothing\npqr3stu8vwx\n\ntreb7uchet";
+
+    #[test]
+    fn test_report_strict() {
+        let err = Report::parse(INPUT, &Vocabulary::digits(), Mode::Strict)
+            .expect_err("report should not parse");
+        assert_eq!(
+            err.to_string(),
+            "line 2: no digit found\nline 4: no digit found"
+        );
+    }
+
+    #[test]
+    fn test_report_lenient() -> Result<()> {
+        let report = Report::parse(INPUT, &Vocabulary::digits(), Mode::Lenient)?;
+        assert_eq!(
+            report.calibrations(),
+            &[
+                Calibration {
+                    line: 1,
+                    first: 1,
+                    last: 2,
+                    value: 12
+                },
+                Calibration {
+                    line: 3,
+                    first: 3,
+                    last: 8,
+                    value: 38
+                },
+                Calibration {
+                    line: 5,
+                    first: 7,
+                    last: 7,
+                    value: 77
+                },
+            ]
+        );
+        let warnings: Vec<usize> = report.warnings().iter().map(|w| w.line).collect();
+        assert_eq!(warnings, vec![2, 4]);
+        assert_eq!(report.total()?, 127);
+        Ok(())
+    }
+
+    #[test]
+    fn test_report_total_overflow() -> Result<()> {
+        // each line is 3_300_000_000, which fits, but two don't
+        let vocabulary = Vocabulary::new([("big", 300_000_000)]);
+        let report = Report::parse("big\nbig", &vocabulary, Mode::Strict)?;
+        assert!(report.total().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_report_value_overflow() {
+        let vocabulary = Vocabulary::new([("big", 400_000_000)]);
+        let err =
+            Report::parse("big", &vocabulary, Mode::Strict).expect_err("value should overflow");
+        assert_eq!(err.to_string(), "line 1: calibration value overflows u32");
+    }
+
+    fn documents() -> impl Strategy<Value = String> {
+        let line = "[a-z]{0,4}[1-9][a-z1-9]{0,4}";
+        prop::collection::vec(line, 0..10).prop_map(|lines| lines.join("\n"))
+    }
+
+    proptest! {
+        #[test]
+        fn test_report_parse_valid(input in documents()) {
+            prop_assert!(Report::parse(&input, &Vocabulary::english(), Mode::Strict).is_ok());
+        }
+
+        #[test]
+        fn test_report_parse_mutated(input in mutated(documents())) {
+            if let Err(err) = Report::parse(&input, &Vocabulary::english(), Mode::Strict) {
+                prop_assert!(is_positioned(&err), "{err}");
+            }
+        }
+    }
+}
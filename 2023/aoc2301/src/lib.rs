@@ -1,2 +1,4 @@
+pub mod calibration;
 pub mod one;
 pub mod two;
+pub mod vocabulary;
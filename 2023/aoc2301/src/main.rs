@@ -1,13 +1,32 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
+
+use aoc2301::{calibration::Mode, one, two};
 
 const INPUT: &str = include_str!("../input");
 
 fn main() -> Result<()> {
-    let first = aoc2301::one::calibrate(INPUT)?;
-    let second = aoc2301::two::calibrate(INPUT)?;
+    let mode = mode(std::env::args().skip(1))?;
+
+    let first = one::report(INPUT, mode)?;
+    let second = two::report(INPUT, mode)?;
+
+    // lenient reports skip bad lines, which would otherwise go unseen
+    for (part, report) in [(1, &first), (2, &second)] {
+        for warning in report.warnings() {
+            eprintln!("warning: part {part} skipped {warning}");
+        }
+    }
 
-    println!("Calibration value 1: {first}");
-    println!("Calibration value 2: {second}");
+    println!("Calibration value 1: {}", first.total()?);
+    println!("Calibration value 2: {}", second.total()?);
 
     Ok(())
 }
+
+fn mode(mut args: impl Iterator<Item = String>) -> Result<Mode> {
+    match args.next().as_deref() {
+        None => Ok(Mode::Strict),
+        Some("--lenient") => Ok(Mode::Lenient),
+        Some(arg) => bail!("unknown argument: {arg} (expected --lenient)"),
+    }
+}
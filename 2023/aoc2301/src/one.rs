@@ -1,24 +1,36 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
+use parse::Failures;
+use registry::aoc;
 
+use crate::{
+    calibration::{self, Mode, Report},
+    vocabulary::Vocabulary,
+};
+
+#[aoc(year = 2023, day = 1, part = 1)]
 pub fn calibrate(input: &str) -> Result<u32> {
-    input.lines().map(evaluate_line).sum()
+    calibration::calibrate(input, &Vocabulary::digits())
 }
 
-fn evaluate_line(line: &str) -> Result<u32> {
-    let first = first_digit(line).with_context(|| "no first digit found")?;
-    let last = last_digit(line).with_context(|| "no first digit found")?;
-
-    let value = format!("{first}{last}")
-        .parse::<u32>()
-        .with_context(|| "parsing calibration value")?;
-
-    Ok(value)
+pub fn report(input: &str, mode: Mode) -> Result<Report, Failures> {
+    Report::parse(input, &Vocabulary::digits(), mode)
 }
 
-fn first_digit(line: &str) -> Option<char> {
-    line.chars().find(|c| c.is_ascii_digit())
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calibrate() {
+        let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+        assert_eq!(calibrate(input).unwrap(), 142)
+    }
 
-fn last_digit(line: &str) -> Option<char> {
-    line.chars().rev().find(|c| c.is_ascii_digit())
+    #[test]
+    fn test_report_lenient() {
+        let input = "1abc2\npqr3stu8vwx\ntrebuchet";
+        let report = report(input, Mode::Lenient).unwrap();
+        assert_eq!(report.total().unwrap(), 50);
+        assert_eq!(report.warnings()[0].to_string(), "line 3: no digit found");
+    }
 }
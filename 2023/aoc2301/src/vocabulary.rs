@@ -0,0 +1,159 @@
+use std::collections::{BTreeMap, VecDeque};
+
+const DIGITS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+const ENGLISH: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// A set of words mapped to values, compiled into an Aho–Corasick automaton
+/// so a line is scanned once and overlapping words like `eightwo` all match.
+#[derive(Debug)]
+pub struct Vocabulary {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    next: BTreeMap<char, usize>,
+    fail: usize,
+    /// Every word ending at this node, including through fail links, as
+    /// its length in chars and its value.
+    outputs: Vec<(usize, u32)>,
+}
+
+impl Vocabulary {
+    pub fn new<S: AsRef<str>>(words: impl IntoIterator<Item = (S, u32)>) -> Vocabulary {
+        let mut nodes = vec![Node::default()];
+
+        for (word, value) in words {
+            let word = word.as_ref();
+            if word.is_empty() {
+                continue;
+            }
+
+            let mut state = 0;
+            for char in word.chars() {
+                state = match nodes[state].next.get(&char) {
+                    Some(next) => *next,
+                    None => {
+                        nodes.push(Node::default());
+                        let next = nodes.len() - 1;
+                        nodes[state].next.insert(char, next);
+                        next
+                    }
+                };
+            }
+            nodes[state].outputs.push((word.chars().count(), value));
+        }
+
+        let mut queue: VecDeque<usize> = nodes[0].next.values().copied().collect();
+        while let Some(state) = queue.pop_front() {
+            let children: Vec<(char, usize)> =
+                nodes[state].next.iter().map(|(c, n)| (*c, *n)).collect();
+            for (char, child) in children {
+                let mut fail = nodes[state].fail;
+                while fail != 0 && !nodes[fail].next.contains_key(&char) {
+                    fail = nodes[fail].fail;
+                }
+                let fail = match nodes[fail].next.get(&char) {
+                    Some(next) if *next != child => *next,
+                    _ => 0,
+                };
+
+                nodes[child].fail = fail;
+                let inherited = nodes[fail].outputs.clone();
+                nodes[child].outputs.extend(inherited);
+                queue.push_back(child);
+            }
+        }
+
+        Vocabulary { nodes }
+    }
+
+    /// Matches only the digits `0` to `9`.
+    pub fn digits() -> Vocabulary {
+        Vocabulary::new(DIGITS)
+    }
+
+    /// Matches the digits plus the English words `one` to `nine`.
+    pub fn english() -> Vocabulary {
+        Vocabulary::new(DIGITS.into_iter().chain(ENGLISH))
+    }
+
+    /// Finds the value of every word in the line, ordered by where each word
+    /// starts, with overlapping words all included.
+    pub fn find(&self, line: &str) -> Vec<u32> {
+        let mut matches = vec![];
+
+        let mut state = 0;
+        for (idx, char) in line.chars().enumerate() {
+            while state != 0 && !self.nodes[state].next.contains_key(&char) {
+                state = self.nodes[state].fail;
+            }
+            state = self.nodes[state].next.get(&char).copied().unwrap_or(0);
+
+            for (len, value) in &self.nodes[state].outputs {
+                matches.push((idx + 1 - len, *len, *value));
+            }
+        }
+
+        matches.sort_unstable();
+        matches.into_iter().map(|(_, _, value)| value).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("a1b2c3d4e5f", vec![1, 2, 3, 4, 5] ; "digits")]
+    #[test_case("eight2three", vec![2] ; "digits ignore words")]
+    #[test_case("", vec![] ; "empty")]
+    fn test_digits_find(input: &str, expected: Vec<u32>) {
+        assert_eq!(Vocabulary::digits().find(input), expected)
+    }
+
+    #[test_case("eightwo", vec![8, 2] ; "eightwo")]
+    #[test_case("oneight", vec![1, 8] ; "oneight")]
+    #[test_case("twoneight", vec![2, 1, 8] ; "twoneight")]
+    #[test_case("sevenine", vec![7, 9] ; "sevenine")]
+    #[test_case("nineeight", vec![9, 8] ; "nineeight")]
+    #[test_case("ninine", vec![9] ; "restart after partial match")]
+    #[test_case("thrthree", vec![3] ; "restart mid word")]
+    fn test_english_find_overlapping(input: &str, expected: Vec<u32>) {
+        assert_eq!(Vocabulary::english().find(input), expected)
+    }
+
+    #[test]
+    fn test_custom_vocabulary() {
+        let vocabulary = Vocabulary::new([("un", 1), ("deux", 2), ("trois", 3), ("zero", 0)]);
+        assert_eq!(vocabulary.find("deuxtroisunzero"), vec![2, 3, 1, 0]);
+    }
+
+    #[test]
+    fn test_nested_words() {
+        let vocabulary = Vocabulary::new([("ten", 10), ("tent", 11), ("en", 12)]);
+        assert_eq!(vocabulary.find("tent"), vec![10, 11, 12]);
+    }
+}
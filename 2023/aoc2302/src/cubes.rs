@@ -1,76 +1,161 @@
-use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
 
-#[derive(Debug, Default)]
+use anyhow::Result;
+use parse::{AocParse, FromSpan, Span};
+
+#[derive(Debug, Default, AocParse)]
+#[parse(format = "Game {id}: {rounds}")]
 pub struct Game {
     pub id: usize,
+    #[parse(separator = ";")]
     pub rounds: Vec<Set>,
 }
 
 impl Game {
-    pub(crate) fn parse(s: &str) -> Result<Game> {
-        let (game, rounds_str) = s.split_once(':').with_context(|| "splitting game line")?;
-
-        let id = game
-            .strip_prefix("Game ")
-            .with_context(|| "stripping game prefix")?
-            .parse()
-            .with_context(|| "parsing game id")?;
-
-        let mut rounds = vec![];
-        for round in rounds_str.split("; ") {
-            rounds.push(Set::parse(round).with_context(|| "parsing round")?)
-        }
-
-        Ok(Game { id, rounds })
+    pub fn parse(s: &str) -> Result<Game> {
+        Ok(s.parse()?)
     }
 
     pub fn possible(&self, bag: &Set) -> bool {
         self.rounds.iter().all(|round| round.possible(bag))
     }
+
+    /// The smallest bag that could have produced every round of the game.
+    pub fn minimum_bag(&self) -> Set {
+        self.rounds
+            .iter()
+            .fold(Set::default(), |bag, round| bag.max(round))
+    }
 }
 
-#[derive(Debug, Default)]
+/// A multiset of cubes keyed by colour name, where any colour not present
+/// counts as zero.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Set {
-    pub red: usize,
-    pub green: usize,
-    pub blue: usize,
+    cubes: BTreeMap<String, usize>,
 }
 
-impl Set {
-    pub fn new(red: usize, green: usize, blue: usize) -> Set {
-        Set { red, green, blue }
+impl<S: Into<String>> FromIterator<(S, usize)> for Set {
+    fn from_iter<T: IntoIterator<Item = (S, usize)>>(iter: T) -> Self {
+        let mut set = Set::default();
+        for (colour, count) in iter {
+            set.insert(colour, count);
+        }
+        set
     }
+}
 
-    fn parse(s: &str) -> Result<Set> {
+/// A round of `count colour` pairs separated by commas.
+impl FromSpan for Set {
+    fn from_span(s: &Span) -> Result<Set, parse::Error> {
         let mut round = Set::default();
 
-        for mut cube in s.split(", ").map(|f| f.split_ascii_whitespace()) {
-            let count = cube
-                .next()
-                .with_context(|| "getting count from iterator")?
-                .parse()
-                .with_context(|| "parsing count")?;
-
-            let colour = cube
-                .next()
-                .with_context(|| "getting colour from iterator")?;
-
-            match colour {
-                "red" => round.red = count,
-                "green" => round.green = count,
-                "blue" => round.blue = count,
-                _ => bail!("invalid colour: {}", colour),
-            }
+        for cube in s.split(",") {
+            let [count, colour] = cube.tokens()[..] else {
+                return Err(cube.trim().error("expected a count and a colour"));
+            };
+            let count: usize = count.number()?;
+
+            let total = round.cubes.entry(colour.as_str().to_string()).or_default();
+            *total = total
+                .checked_add(count)
+                .ok_or_else(|| colour.error(format!("too many {} cubes", colour.as_str())))?;
         }
 
         Ok(round)
     }
+}
+
+impl Set {
+    pub fn insert(&mut self, colour: impl Into<String>, count: usize) {
+        self.cubes.insert(colour.into(), count);
+    }
+
+    pub fn count(&self, colour: &str) -> usize {
+        self.cubes.get(colour).copied().unwrap_or_default()
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = &str> {
+        self.cubes.keys().map(String::as_str)
+    }
 
     fn possible(&self, bag: &Set) -> bool {
-        self.red <= bag.red && self.green <= bag.green && self.blue <= bag.blue
+        self.cubes
+            .iter()
+            .all(|(colour, count)| *count <= bag.count(colour))
+    }
+
+    /// Takes the larger count of each colour from either set.
+    pub fn max(&self, other: &Set) -> Set {
+        let mut set = self.clone();
+        for (colour, count) in &other.cubes {
+            let entry = set.cubes.entry(colour.clone()).or_default();
+            *entry = (*entry).max(*count);
+        }
+        set
+    }
+
+    /// Multiplies the counts of the given colours, so any colour missing from
+    /// the set makes the power zero.
+    pub fn power(&self, colours: &[&str]) -> usize {
+        colours.iter().map(|colour| self.count(colour)).product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn test_game_parse_any_colour() -> Result<()> {
+        let game = Game::parse("Game 7: 3 teal, 4 red; 1 teal, 2 mauve; 5 red")?;
+        let expected = Set::from_iter([("teal", 3), ("red", 5), ("mauve", 2)]);
+        assert_eq!(game.id, 7);
+        assert_eq!(game.rounds.len(), 3);
+        assert_eq!(game.minimum_bag(), expected);
+        assert_eq!(game.minimum_bag().power(&["teal", "red", "mauve"]), 30);
+        Ok(())
+    }
+
+    #[test]
+    fn test_game_possible_any_colour() -> Result<()> {
+        let game = Game::parse("Game 1: 3 teal, 4 red; 1 teal, 2 mauve")?;
+        let bag = Set::from_iter([("teal", 3), ("red", 4), ("mauve", 2)]);
+        assert!(game.possible(&bag));
+
+        let bag = Set::from_iter([("teal", 3), ("red", 4)]);
+        assert!(!game.possible(&bag), "missing colour counts as zero");
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_parse_repeated_colour() -> Result<()> {
+        let game = Game::parse("Game 1: 3 red, 4 red")?;
+        assert_eq!(game.rounds[0].count("red"), 7);
+        Ok(())
+    }
+
+    #[test_case("Game 1: 3 red, 4", "line 1, column 16: expected a count and a colour" ; "missing colour")]
+    #[test_case("Game 1: 3 red; x blue", "line 1, column 16: invalid number \"x\": invalid digit found in string" ; "bad count")]
+    #[test_case("Game x: 3 red", "line 1, column 6: invalid number \"x\": invalid digit found in string" ; "bad id")]
+    #[test_case("Game 1: 18446744073709551615 red, 1 red", "line 1, column 37: too many red cubes" ; "overflow")]
+    fn test_game_parse_error(input: &str, expected: &str) {
+        let err = Game::parse(input).expect_err("game should not parse");
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[test_case("Game 1: 3 red, 4 green; 1 red", 0 ; "missing colour")]
+    #[test_case("Game 1: 3 red, 4 green; 1 red, 2 blue", 24 ; "every colour")]
+    #[test_case("Game 1: 3 red, 4 green, 5 teal; 2 blue", 24 ; "other colour")]
+    fn test_set_power(input: &str, expected: usize) -> Result<()> {
+        let bag = Game::parse(input)?.minimum_bag();
+        assert_eq!(bag.power(&["red", "green", "blue"]), expected);
+        Ok(())
     }
 
-    pub fn power(&self) -> usize {
-        self.red * self.green * self.blue
+    #[test]
+    fn test_set_power_empty() {
+        assert_eq!(Set::default().power(&["red", "green", "blue"]), 0);
     }
 }
@@ -1,4 +1,4 @@
+pub mod cubes;
+pub mod log;
 pub mod one;
 pub mod two;
-
-mod cubes;
@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+
+use parse::FromSpan;
+
+use crate::cubes::{Game, Set};
+
+#[derive(Debug, Default)]
+pub struct GameLog {
+    games: Vec<Game>,
+}
+
+impl GameLog {
+    pub fn new(games: Vec<Game>) -> GameLog {
+        GameLog { games }
+    }
+
+    pub fn parse(s: &str) -> Result<GameLog> {
+        let games = parse::lines(s)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                Game::from_span(&line)
+                    .with_context(|| format!("parsing game on line {}", line.line()))
+            })
+            .collect::<Result<Vec<Game>>>()?;
+
+        Ok(GameLog::new(games))
+    }
+
+    pub fn games(&self) -> &[Game] {
+        &self.games
+    }
+
+    pub fn possible<'a>(&'a self, bag: &'a Set) -> impl Iterator<Item = &'a Game> {
+        self.games.iter().filter(|game| game.possible(bag))
+    }
+
+    /// The smallest bag that could have produced every game in the log.
+    pub fn minimum_bag(&self) -> Set {
+        self.games
+            .iter()
+            .fold(Set::default(), |bag, game| bag.max(&game.minimum_bag()))
+    }
+
+    /// Lists the ids of games possible with `bag` that become impossible once
+    /// it holds `k` fewer cubes of `colour`.
+    pub fn impossible_if_reduced(&self, bag: &Set, colour: &str, k: usize) -> Vec<usize> {
+        let mut reduced = bag.clone();
+        reduced.insert(colour, bag.count(colour).saturating_sub(k));
+
+        self.possible(bag)
+            .filter(|game| !game.possible(&reduced))
+            .map(|game| game.id)
+            .collect()
+    }
+
+    /// Counts, for each colour, how many games had each maximum draw. Games
+    /// that never drew a colour count towards a maximum of zero.
+    pub fn max_draw_histogram(&self) -> BTreeMap<String, BTreeMap<usize, usize>> {
+        let bags: Vec<Set> = self.games.iter().map(Game::minimum_bag).collect();
+
+        let mut histogram: BTreeMap<String, BTreeMap<usize, usize>> = BTreeMap::new();
+        for colour in self.minimum_bag().colours() {
+            let counts = histogram.entry(colour.to_string()).or_default();
+            for bag in &bags {
+                *counts.entry(bag.count(colour)).or_default() += 1;
+            }
+        }
+        histogram
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parse::testing::{is_positioned, mutated};
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const EXAMPLE: &str = "
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+    fn bag() -> Set {
+        Set::from_iter([("red", 12), ("green", 13), ("blue", 14)])
+    }
+
+    #[test]
+    fn test_game_log_possible() -> Result<()> {
+        let log = GameLog::parse(EXAMPLE)?;
+        let bag = bag();
+        let ids: Vec<usize> = log.possible(&bag).map(|g| g.id).collect();
+        assert_eq!(ids, vec![1, 2, 5]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_game_log_minimum_bag() -> Result<()> {
+        let log = GameLog::parse(EXAMPLE)?;
+        let expected = Set::from_iter([("red", 20), ("green", 13), ("blue", 15)]);
+        assert_eq!(log.minimum_bag(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_game_log_impossible_if_reduced() -> Result<()> {
+        let log = GameLog::parse(EXAMPLE)?;
+        assert_eq!(log.impossible_if_reduced(&bag(), "red", 7), vec![5]);
+        assert_eq!(log.impossible_if_reduced(&bag(), "blue", 9), vec![1]);
+        assert_eq!(log.impossible_if_reduced(&bag(), "green", 11), vec![2, 5]);
+        assert_eq!(log.impossible_if_reduced(&bag(), "red", 0), vec![]);
+        assert_eq!(log.impossible_if_reduced(&bag(), "teal", 3), vec![]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_game_log_max_draw_histogram() -> Result<()> {
+        let log = GameLog::parse(EXAMPLE)?;
+        let histogram = log.max_draw_histogram();
+        let expected = BTreeMap::from([
+            (
+                "blue".to_string(),
+                BTreeMap::from([(2, 1), (4, 1), (6, 2), (15, 1)]),
+            ),
+            (
+                "green".to_string(),
+                BTreeMap::from([(2, 1), (3, 3), (13, 1)]),
+            ),
+            (
+                "red".to_string(),
+                BTreeMap::from([(1, 1), (4, 1), (6, 1), (14, 1), (20, 1)]),
+            ),
+        ]);
+        assert_eq!(histogram, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_game_log_parse_error_line() {
+        let err = GameLog::parse("Game 1: 1 red\nGame x: 2 red").expect_err("log should not parse");
+        assert_eq!(err.to_string(), "parsing game on line 2");
+    }
+
+    fn logs() -> impl Strategy<Value = String> {
+        let cube = (
+            0..20usize,
+            prop::sample::select(&["red", "green", "blue"][..]),
+        );
+        let round = prop::collection::vec(cube, 1..4);
+        let game = prop::collection::vec(round, 1..4);
+        prop::collection::vec(game, 0..8).prop_map(|games| {
+            games
+                .iter()
+                .enumerate()
+                .map(|(idx, rounds)| {
+                    let rounds: Vec<String> = rounds
+                        .iter()
+                        .map(|cubes| {
+                            let cubes: Vec<String> = cubes
+                                .iter()
+                                .map(|(count, colour)| format!("{count} {colour}"))
+                                .collect();
+                            cubes.join(", ")
+                        })
+                        .collect();
+                    format!("Game {}: {}\n", idx + 1, rounds.join("; "))
+                })
+                .collect()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_game_log_parse_valid(input in logs()) {
+            prop_assert!(GameLog::parse(&input).is_ok());
+        }
+
+        #[test]
+        fn test_game_log_parse_mutated(input in mutated(logs())) {
+            if let Err(err) = GameLog::parse(&input) {
+                prop_assert!(is_positioned(err.as_ref()), "{err:#}");
+            }
+        }
+    }
+}
@@ -1,10 +1,10 @@
-use anyhow::{Context, Result};
+use anyhow::Context;
 
 use aoc2302::{one, two};
 
 const INPUT: &str = include_str!("../input");
 
-fn main() -> Result<()> {
+fn main() -> Result<(), parse::Report> {
     let possible = one::sum_possible(INPUT).with_context(|| "summing possible games")?;
     let power = two::sum_sets_power(INPUT).with_context(|| "summing sets powers")?;
 
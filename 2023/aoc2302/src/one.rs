@@ -1,19 +1,13 @@
 use anyhow::{Context, Result};
+use registry::aoc;
 
-use crate::cubes::{Game, Set};
+use crate::{cubes::Set, log::GameLog};
 
+#[aoc(year = 2023, day = 2, part = 1)]
 pub fn sum_possible(s: &str) -> Result<usize> {
-    let mut count = 0;
+    let bag = Set::from_iter([("red", 12), ("green", 13), ("blue", 14)]);
 
-    let bag = Set::new(12, 13, 14);
+    let log = GameLog::parse(s).with_context(|| "parsing games")?;
 
-    for line in s.lines() {
-        let game = Game::parse(line).with_context(|| "parsing game")?;
-
-        if game.possible(&bag) {
-            count += game.id
-        }
-    }
-
-    Ok(count)
+    Ok(log.possible(&bag).map(|game| game.id).sum())
 }
@@ -1,28 +1,18 @@
 use anyhow::{Context, Result};
+use registry::aoc;
 
-use crate::cubes::{Game, Set};
+use crate::log::GameLog;
 
-pub fn sum_sets_power(s: &str) -> Result<usize> {
-    let mut count = 0;
+/// The colours each game's power is taken over, whether it shows them or not.
+const COLOURS: [&str; 3] = ["red", "green", "blue"];
 
-    for line in s.lines() {
-        let mut bag = Set::default();
-        let game = Game::parse(line).with_context(|| "parsing game")?;
-        
-        for round in game.rounds {
-            if round.red > bag.red {
-                bag.red = round.red
-            }
-            if round.green > bag.green {
-                bag.green = round.green
-            }
-            if round.blue > bag.blue {
-                bag.blue = round.blue
-            }
-        }
-        
-        count += bag.power()
-    }
+#[aoc(year = 2023, day = 2, part = 2)]
+pub fn sum_sets_power(s: &str) -> Result<usize> {
+    let log = GameLog::parse(s).with_context(|| "parsing games")?;
 
-    Ok(count)
+    Ok(log
+        .games()
+        .iter()
+        .map(|game| game.minimum_bag().power(&COLOURS))
+        .sum())
 }
@@ -0,0 +1,59 @@
+use std::hint::black_box;
+
+use aoc2303::{one, two};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const SYMBOLS: &[u8] = b"*#+$/=@%&-";
+
+/// Builds a square schematic with a realistic mix of numbers, symbols and
+/// blanks, using a fixed linear congruential generator so runs are comparable.
+fn synthetic_schematic(size: usize) -> String {
+    let mut state: u64 = 0x2303;
+    let mut next = move || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) as usize
+    };
+
+    let mut schematic = String::with_capacity(size * (size + 1));
+    for _ in 0..size {
+        let mut c = 0;
+        while c < size {
+            match next() % 10 {
+                0..=1 if c + 3 < size => {
+                    let width = 1 + next() % 3;
+                    for _ in 0..width {
+                        schematic.push((b'0' + (next() % 10) as u8) as char);
+                    }
+                    schematic.push('.');
+                    c += width + 1;
+                }
+                2 => {
+                    schematic.push(SYMBOLS[next() % SYMBOLS.len()] as char);
+                    c += 1;
+                }
+                _ => {
+                    schematic.push('.');
+                    c += 1;
+                }
+            }
+        }
+        schematic.push('\n');
+    }
+    schematic
+}
+
+fn engine_benchmark(c: &mut Criterion) {
+    let schematic = synthetic_schematic(1_000);
+
+    c.bench_function("sum parts 1000x1000", |b| {
+        b.iter(|| one::sum_parts(black_box(&schematic)).unwrap())
+    });
+    c.bench_function("sum gear ratios 1000x1000", |b| {
+        b.iter(|| two::sum_gear_ratios(black_box(&schematic)).unwrap())
+    });
+}
+
+criterion_group!(benches, engine_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,62 @@
+use std::fmt::{self, Display};
+
+/// Controls how characters in a schematic are classified. Each character is
+/// tested as a digit, then a blank, then a symbol; anything else is
+/// unexpected and is either reported as a warning or fails the parse.
+#[derive(Clone, Debug)]
+pub struct SchematicConfig {
+    pub blanks: Vec<char>,
+    pub symbol: fn(char) -> bool,
+    pub digit: fn(char) -> Option<usize>,
+    pub strict: bool,
+}
+
+impl Default for SchematicConfig {
+    fn default() -> Self {
+        SchematicConfig {
+            blanks: vec!['.'],
+            symbol: |c| c.is_ascii_punctuation(),
+            digit: |c| c.to_digit(10).map(|d| d as usize),
+            strict: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Class {
+    Digit(usize),
+    Blank,
+    Symbol,
+    Unexpected,
+}
+
+impl SchematicConfig {
+    pub(crate) fn classify(&self, char: char) -> Class {
+        if let Some(digit) = (self.digit)(char) {
+            Class::Digit(digit)
+        } else if self.blanks.contains(&char) {
+            Class::Blank
+        } else if (self.symbol)(char) {
+            Class::Symbol
+        } else {
+            Class::Unexpected
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseWarning {
+    pub line: usize,
+    pub column: usize,
+    pub char: char,
+}
+
+impl Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: unexpected character {:?}",
+            self.line, self.column, self.char
+        )
+    }
+}
@@ -1,98 +1,206 @@
 use std::ops::Range;
 
+use anyhow::{bail, Context, Result};
+
+use crate::config::{Class, ParseWarning, SchematicConfig};
+
 #[derive(Debug, Default)]
 pub struct Engine {
     parts: Vec<Part>,
     symbols: Vec<Symbol>,
+    grid: Vec<Vec<Cell>>,
+    /// The schematic's characters, kept alongside the grid for rendering.
+    chars: Vec<Vec<char>>,
+    config: SchematicConfig,
+    warnings: Vec<ParseWarning>,
+}
+
+/// What occupies a position in the schematic, holding an index into the
+/// engine's parts or symbols so neighbours can be resolved without scanning.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum Cell {
+    #[default]
+    Empty,
+    Part(usize),
+    Symbol(usize),
 }
 
 impl Engine {
-    pub fn parse(s: &str) -> anyhow::Result<Engine> {
-        let mut engine = Engine::default();
+    pub fn parse(s: &str) -> Result<Engine> {
+        Engine::parse_with(s, &SchematicConfig::default())
+    }
 
-        for (l, line) in s.lines().enumerate() {
-            for (c, char) in line.char_indices() {
-                if char.is_ascii_punctuation() && char != '.' {
-                    let location = Location::from_usize(l, c);
-                    let symbol = Symbol::new(char, location);
-                    engine.symbols.push(symbol)
-                }
-            }
-        }
+    pub fn parse_with(s: &str, config: &SchematicConfig) -> Result<Engine> {
+        let mut engine = Engine {
+            config: config.clone(),
+            ..Engine::default()
+        };
 
         for (l, line) in s.lines().enumerate() {
-            let mut chars = line.char_indices().peekable();
-            while let Some((c, char)) = chars.next() {
-                if char.is_ascii_digit() {
-                    let mut location = Location::from_usize(l, c);
-
-                    let adjacent_to_symbol = engine
-                        .symbols
-                        .iter()
-                        .any(|l| l.location.adjacent(&location));
-
-                    if !adjacent_to_symbol {
-                        continue;
+            let mut row = vec![];
+            for (c, char) in line.chars().enumerate() {
+                let cell = match config.classify(char) {
+                    Class::Symbol => {
+                        let location = Location::from_usize(l, c);
+                        engine.symbols.push(Symbol::new(char, location));
+                        Cell::Symbol(engine.symbols.len() - 1)
                     }
-
-                    let mut part = char.to_string();
-
-                    if let Some(left) = line.get(0..c) {
-                        let i = left
-                            .rfind(|c: char| !c.is_ascii_digit())
-                            .map(|i| i + 1)
-                            .unwrap_or_default();
-                        if let Some(s) = line.get(i..c) {
-                            part.insert_str(0, s)
+                    Class::Unexpected => {
+                        let warning = ParseWarning {
+                            line: l + 1,
+                            column: c + 1,
+                            char,
+                        };
+                        if config.strict {
+                            bail!("{warning}")
                         }
-                        location.range.start = i;
+                        engine.warnings.push(warning);
+                        Cell::Empty
                     }
+                    Class::Digit(_) | Class::Blank => Cell::Empty,
+                };
+                row.push(cell);
+            }
+            engine.grid.push(row);
+            engine.chars.push(line.chars().collect());
+        }
 
-                    while let Some((_, char)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
-                        part.push(char);
-                        location.range.end += 1;
-                    }
+        for (l, line) in s.lines().enumerate() {
+            let mut chars = line
+                .chars()
+                .enumerate()
+                .map(|(c, char)| (c, char, config.classify(char)))
+                .peekable();
+            while let Some((start, _, class)) = chars.next() {
+                let Class::Digit(digit) = class else {
+                    continue;
+                };
+
+                let mut end = start + 1;
+                let mut id = Some(digit);
+                while let Some((c, _, Class::Digit(digit))) =
+                    chars.next_if(|(_, _, class)| matches!(class, Class::Digit(_)))
+                {
+                    id = id
+                        .and_then(|id| id.checked_mul(10))
+                        .and_then(|id| id.checked_add(digit));
+                    end = c + 1;
+                }
+                let id = id.with_context(|| {
+                    format!("line {}, column {}: number too large", l + 1, start + 1)
+                })?;
+
+                let adjacent_to_symbol = (start..end).any(|c| {
+                    engine
+                        .neighbours(l, c)
+                        .any(|cell| matches!(cell, Cell::Symbol(_)))
+                });
+
+                if !adjacent_to_symbol {
+                    continue;
+                }
 
-                    engine.parts.push(Part::new(part.parse()?, location))
+                for cell in &mut engine.grid[l][start..end] {
+                    *cell = Cell::Part(engine.parts.len());
                 }
+                let location = Location::from_range_usize(l, start..end);
+                engine.parts.push(Part::new(id, location))
             }
         }
 
         Ok(engine)
     }
 
-    pub fn sum_parts(&self) -> usize {
-        self.parts.iter().fold(0, |a, b| a + b.id)
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
     }
 
-    pub fn sum_gear_ratios(&self) -> usize {
-        let mut sum = 0;
+    fn neighbours(&self, line: usize, index: usize) -> impl Iterator<Item = Cell> + '_ {
+        let lines = line.saturating_sub(1)..=line + 1;
+        lines.flat_map(move |l| {
+            let row = self.grid.get(l).map(Vec::as_slice).unwrap_or_default();
+            let start = index.saturating_sub(1).min(row.len());
+            let end = (index + 2).min(row.len());
+            row[start..end]
+                .iter()
+                .enumerate()
+                .filter(move |(c, _)| (l, start + c) != (line, index))
+                .map(|(_, cell)| *cell)
+        })
+    }
 
-        for symbol in &self.symbols {
-            if symbol.char != '*' {
-                continue;
-            }
+    pub(crate) fn cell(&self, line: usize, index: usize) -> Option<Cell> {
+        self.grid.get(line)?.get(index).copied()
+    }
 
-            let adjacent_numbers = self
-                .parts
-                .iter()
-                .filter(|p| p.location.adjacent(&symbol.location))
-                .map(|p| p.id)
-                .collect::<Vec<usize>>();
+    pub(crate) fn chars(&self) -> &[Vec<char>] {
+        &self.chars
+    }
 
-            if adjacent_numbers.len() != 2 {
-                continue;
-            }
+    pub(crate) fn config(&self) -> &SchematicConfig {
+        &self.config
+    }
+
+    pub fn parts(&self) -> &[Part] {
+        &self.parts
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
 
-            sum += adjacent_numbers[0] * adjacent_numbers[1]
+    /// Lists each distinct part touching the symbol, in the order the
+    /// neighbouring cells are visited.
+    pub fn adjacent_parts(&self, symbol: &Symbol) -> Vec<&Part> {
+        let mut adjacent = vec![];
+        for cell in self.neighbours(symbol.location.line, symbol.location.range.start) {
+            if let Cell::Part(idx) = cell {
+                if !adjacent.contains(&idx) {
+                    adjacent.push(idx);
+                }
+            }
         }
+        adjacent.into_iter().map(|idx| &self.parts[idx]).collect()
+    }
+
+    /// Finds every `char` symbol touching exactly `count` parts.
+    pub fn symbols_with_parts(
+        &self,
+        char: char,
+        count: usize,
+    ) -> impl Iterator<Item = (&Symbol, Vec<&Part>)> + '_ {
+        self.symbols
+            .iter()
+            .filter(move |symbol| symbol.char == char)
+            .map(|symbol| (symbol, self.adjacent_parts(symbol)))
+            .filter(move |(_, parts)| parts.len() == count)
+    }
 
-        sum
+    /// Folds `reducer` over every `char` symbol touching exactly `count` parts.
+    pub fn fold_symbols<T>(
+        &self,
+        char: char,
+        count: usize,
+        init: T,
+        mut reducer: impl FnMut(T, &Symbol, &[&Part]) -> T,
+    ) -> T {
+        self.symbols_with_parts(char, count)
+            .fold(init, |acc, (symbol, parts)| reducer(acc, symbol, &parts))
+    }
+
+    pub fn sum_parts(&self) -> usize {
+        self.parts.iter().fold(0, |a, b| a + b.id)
+    }
+
+    pub fn sum_gear_ratios(&self) -> usize {
+        self.fold_symbols('*', 2, 0, |sum, _, parts| {
+            sum + parts.iter().map(|p| p.id).product::<usize>()
+        })
     }
 }
 
 #[derive(Debug, Default, Eq, PartialEq)]
-struct Part {
+pub struct Part {
     id: usize,
     location: Location,
 }
@@ -101,10 +209,18 @@ impl Part {
     fn new(id: usize, location: Location) -> Part {
         Part { id, location }
     }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn location(&self) -> &Location {
+        &self.location
+    }
 }
 
 #[derive(Debug, Default, Eq, PartialEq)]
-struct Symbol {
+pub struct Symbol {
     char: char,
     location: Location,
 }
@@ -113,16 +229,31 @@ impl Symbol {
     fn new(char: char, location: Location) -> Symbol {
         Symbol { char, location }
     }
+
+    pub fn char(&self) -> char {
+        self.char
+    }
+
+    pub fn location(&self) -> &Location {
+        &self.location
+    }
 }
 
 #[derive(Debug, Default, Eq, PartialEq)]
-struct Location {
+pub struct Location {
     line: usize,
     range: Range<usize>,
 }
 
 impl Location {
-    #[cfg(test)]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
     pub fn from_range_usize(line: usize, range: Range<usize>) -> Location {
         Location { line, range }
     }
@@ -131,23 +262,14 @@ impl Location {
         let range = index..index + 1;
         Location { line, range }
     }
-
-    pub fn adjacent(&self, other: &Location) -> bool {
-        let line_adjacent = self.line.abs_diff(other.line) <= 1;
-        let char_adjacent = ranges_adjacent(&self.range, &other.range);
-        self != other && line_adjacent && char_adjacent
-    }
-}
-
-fn ranges_adjacent(one: &Range<usize>, two: &Range<usize>) -> bool {
-    let start = one.start.checked_sub(1).unwrap_or(one.start);
-    let end = one.end.checked_add(1).unwrap_or(one.end);
-    (start..end).any(|i| two.contains(&i))
 }
 
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
+    use parse::testing::{is_positioned, mutated};
+    use proptest::prelude::*;
+    use test_case::test_case;
 
     use super::*;
 
@@ -223,4 +345,167 @@ mod tests {
 
         Ok(())
     }
+
+    #[test_case("123\n..*", vec![(123, 0..3)] ; "symbol touching last digit")]
+    #[test_case("*..\n.45", vec![(45, 1..3)] ; "symbol touching first digit only")]
+    #[test_case("12345\n..#..", vec![(12345, 0..5)] ; "symbol touching middle digit")]
+    #[test_case("....12\n.....%", vec![(12, 4..6)] ; "number at line end")]
+    #[test_case("7...\n$...", vec![(7, 0..1)] ; "number at line start")]
+    #[test_case("..$\n99.", vec![(99, 0..2)] ; "number below symbol at diagonal")]
+    #[test_case("1.2\n...\n3+4", vec![(3, 0..1), (4, 2..3)] ; "symbol between numbers")]
+    #[test_case("123\n...\n..*", vec![] ; "symbol two lines away")]
+    #[test_case("12.*", vec![] ; "symbol one gap away")]
+    fn test_engine_parse_part_spans(
+        input: &str,
+        expected: Vec<(usize, Range<usize>)>,
+    ) -> Result<()> {
+        let engine = Engine::parse(input)?;
+        let parts: Vec<(usize, Range<usize>)> = engine
+            .parts()
+            .iter()
+            .map(|p| (p.id(), p.location().range()))
+            .collect();
+        assert_eq!(parts, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_engine_parse_warnings() -> Result<()> {
+        let engine = Engine::parse("12a..\n..é*4")?;
+        let warnings: Vec<String> = engine.warnings().iter().map(|w| w.to_string()).collect();
+        let expected = vec![
+            "line 1, column 3: unexpected character 'a'",
+            "line 2, column 3: unexpected character 'é'",
+        ];
+        assert_eq!(warnings, expected);
+        assert_eq!(engine.sum_parts(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_engine_parse_strict() {
+        let config = SchematicConfig {
+            strict: true,
+            ..Default::default()
+        };
+        let err = Engine::parse_with("12.\n.x*", &config).expect_err("parse should fail");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: unexpected character 'x'"
+        );
+    }
+
+    #[test]
+    fn test_engine_parse_with_config() -> Result<()> {
+        let config = SchematicConfig {
+            blanks: vec![' ', '.'],
+            symbol: |c| !c.is_alphanumeric(),
+            strict: true,
+            ..Default::default()
+        };
+        let engine = Engine::parse_with("12★ .\n.. 7.\n5....", &config)?;
+        assert_eq!(engine.sum_parts(), 19);
+        assert_eq!(engine.symbols()[0].location().range(), 2..3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_engine_parse_custom_digits() -> Result<()> {
+        let config = SchematicConfig {
+            digit: |c| match c {
+                '０'..='９' => Some(c as usize - '０' as usize),
+                _ => c.to_digit(10).map(|d| d as usize),
+            },
+            ..Default::default()
+        };
+        let engine = Engine::parse_with("１２3.\n...#", &config)?;
+        assert_eq!(engine.sum_parts(), 123);
+        Ok(())
+    }
+
+    #[test]
+    fn test_engine_parse_number_too_large() {
+        let err = Engine::parse("..99999999999999999999999*").expect_err("parse should fail");
+        assert_eq!(err.to_string(), "line 1, column 3: number too large");
+    }
+
+    const EXAMPLE: &str = "
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn test_engine_adjacent_parts() -> Result<()> {
+        let engine = Engine::parse(EXAMPLE.trim())?;
+
+        let adjacent: Vec<Vec<usize>> = engine
+            .symbols()
+            .iter()
+            .map(|s| engine.adjacent_parts(s).iter().map(|p| p.id()).collect())
+            .collect();
+        let expected = vec![
+            vec![467, 35],
+            vec![633],
+            vec![617],
+            vec![592],
+            vec![664],
+            vec![755, 598],
+        ];
+        assert_eq!(adjacent, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_engine_symbols_with_parts() -> Result<()> {
+        let engine = Engine::parse(EXAMPLE.trim())?;
+
+        let lonely_gears: Vec<usize> = engine
+            .symbols_with_parts('*', 1)
+            .map(|(s, _)| s.location().line())
+            .collect();
+        assert_eq!(lonely_gears, vec![4]);
+
+        let singles = engine.fold_symbols('#', 1, vec![], |mut acc, _, parts| {
+            acc.extend(parts.iter().map(|p| (p.id(), p.location().range())));
+            acc
+        });
+        assert_eq!(singles, vec![(633, 6..9)]);
+
+        let largest = engine.fold_symbols('*', 2, 0, |max, _, parts| {
+            parts.iter().map(|p| p.id()).fold(max, usize::max)
+        });
+        assert_eq!(largest, 755);
+
+        Ok(())
+    }
+
+    fn schematics() -> impl Strategy<Value = String> {
+        prop::collection::vec("[.0-9*#+$]{0,12}", 0..10).prop_map(|rows| rows.join("\n"))
+    }
+
+    proptest! {
+        #[test]
+        fn test_engine_parse_valid(input in schematics()) {
+            prop_assert!(Engine::parse(&input).is_ok());
+        }
+
+        #[test]
+        fn test_engine_parse_mutated(input in mutated(schematics())) {
+            let strict = SchematicConfig {
+                strict: true,
+                ..Default::default()
+            };
+            if let Err(err) = Engine::parse_with(&input, &strict) {
+                prop_assert!(is_positioned(err.as_ref()), "{err:#}");
+            }
+        }
+    }
 }
@@ -1,4 +1,5 @@
+pub mod config;
+pub mod engine;
 pub mod one;
+pub mod render;
 pub mod two;
-
-mod engine;
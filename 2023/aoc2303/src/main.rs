@@ -1,10 +1,15 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 
-use aoc2303::{one, two};
+use aoc2303::{engine::Engine, one, render::Format, two};
 
 const INPUT: &str = include_str!("../input");
 
 fn main() -> Result<()> {
+    if let Some(format) = render_format(std::env::args().skip(1))? {
+        print!("{}", Engine::parse(INPUT)?.render(format));
+        return Ok(());
+    }
+
     let sum_parts = one::sum_parts(INPUT)?;
     let sum_gears = two::sum_gear_ratios(INPUT)?;
 
@@ -13,3 +18,15 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+fn render_format(mut args: impl Iterator<Item = String>) -> Result<Option<Format>> {
+    let Some(arg) = args.next() else {
+        return Ok(None);
+    };
+
+    match arg.as_str() {
+        "--render" | "--render=ansi" => Ok(Some(Format::Ansi)),
+        "--render=html" => Ok(Some(Format::Html)),
+        _ => bail!("unknown argument: {arg} (expected --render, --render=ansi or --render=html)"),
+    }
+}
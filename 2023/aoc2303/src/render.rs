@@ -0,0 +1,148 @@
+use crate::engine::{Cell, Engine};
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    #[default]
+    Ansi,
+    Html,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Mark {
+    Part,
+    Number,
+    Gear,
+}
+
+impl Mark {
+    fn ansi(&self) -> &'static str {
+        match self {
+            Mark::Part => "\x1b[32m",
+            Mark::Number => "\x1b[31m",
+            Mark::Gear => "\x1b[1;30;43m",
+        }
+    }
+
+    fn html(&self) -> &'static str {
+        match self {
+            Mark::Part => "color: green",
+            Mark::Number => "color: red",
+            Mark::Gear => "background: gold; font-weight: bold",
+        }
+    }
+}
+
+impl Engine {
+    /// Renders the schematic back to text, with parts in green, numbers that
+    /// are not parts in red, and gears highlighted.
+    pub fn render(&self, format: Format) -> String {
+        let mut out = String::new();
+
+        if format == Format::Html {
+            out.push_str("<pre class=\"schematic\">\n");
+        }
+
+        for (l, line) in self.chars().iter().enumerate() {
+            let mut spans: Vec<(Option<Mark>, String)> = vec![];
+            for (c, &char) in line.iter().enumerate() {
+                let mark = self.mark(l, c, char);
+                match spans.last_mut() {
+                    Some((last, span)) if *last == mark => span.push(char),
+                    _ => spans.push((mark, char.to_string())),
+                }
+            }
+
+            for (mark, span) in spans {
+                match (format, mark) {
+                    (Format::Ansi, None) => out.push_str(&span),
+                    (Format::Ansi, Some(mark)) => {
+                        out.push_str(mark.ansi());
+                        out.push_str(&span);
+                        out.push_str("\x1b[0m");
+                    }
+                    (Format::Html, None) => out.push_str(&escape_html(&span)),
+                    (Format::Html, Some(mark)) => {
+                        out.push_str("<span style=\"");
+                        out.push_str(mark.html());
+                        out.push_str("\">");
+                        out.push_str(&escape_html(&span));
+                        out.push_str("</span>");
+                    }
+                }
+            }
+            out.push('\n');
+        }
+
+        if format == Format::Html {
+            out.push_str("</pre>\n");
+        }
+
+        out
+    }
+
+    /// How to highlight the character, or `None` to leave it plain.
+    fn mark(&self, line: usize, index: usize, char: char) -> Option<Mark> {
+        match self.cell(line, index)? {
+            Cell::Part(_) => Some(Mark::Part),
+            Cell::Symbol(idx) => {
+                let symbol = &self.symbols()[idx];
+                let gear = symbol.char() == '*' && self.adjacent_parts(symbol).len() == 2;
+                gear.then_some(Mark::Gear)
+            }
+            Cell::Empty if (self.config().digit)(char).is_some() => Some(Mark::Number),
+            Cell::Empty => None,
+        }
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+    use crate::config::SchematicConfig;
+
+    const INPUT: &str = "467..114..\n...*......\n..35.<&633";
+
+    #[test]
+    fn test_render_ansi() -> Result<()> {
+        let engine = Engine::parse(INPUT)?;
+        let expected = "\x1b[32m467\x1b[0m..\x1b[31m114\x1b[0m..\n\
+            ...\x1b[1;30;43m*\x1b[0m......\n\
+            ..\x1b[32m35\x1b[0m.<&\x1b[32m633\x1b[0m\n";
+        assert_eq!(engine.render(Format::Ansi), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_html() -> Result<()> {
+        let engine = Engine::parse(INPUT)?;
+        let expected = "<pre class=\"schematic\">\n\
+            <span style=\"color: green\">467</span>..<span style=\"color: red\">114</span>..\n\
+            ...<span style=\"background: gold; font-weight: bold\">*</span>......\n\
+            ..<span style=\"color: green\">35</span>.&lt;&amp;<span style=\"color: green\">633</span>\n\
+            </pre>\n";
+        assert_eq!(engine.render(Format::Html), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_custom_digits() -> Result<()> {
+        // letters a to j stand for the digits 0 to 9
+        let config = SchematicConfig {
+            digit: |c| ('a'..='j').contains(&c).then(|| c as usize - 'a' as usize),
+            ..SchematicConfig::default()
+        };
+        let engine = Engine::parse_with("bc..de\n.*....", &config)?;
+        let expected = "\x1b[32mbc\x1b[0m..\x1b[31mde\x1b[0m\n\
+            .*....\n";
+        assert_eq!(engine.render(Format::Ansi), expected);
+        Ok(())
+    }
+}
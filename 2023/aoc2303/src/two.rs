@@ -1,7 +1,9 @@
 use anyhow::Result;
+use registry::aoc;
 
 use crate::engine::Engine;
 
+#[aoc(year = 2023, day = 3, part = 2)]
 pub fn sum_gear_ratios(s: &str) -> Result<usize> {
     let engine = Engine::parse(s)?;
 
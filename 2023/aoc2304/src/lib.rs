@@ -1,4 +1,3 @@
 pub mod one;
+pub mod scratchcards;
 pub mod two;
-
-mod scratchcards;
@@ -1,9 +1,9 @@
-use anyhow::{Context, Result};
+use anyhow::Context;
 use aoc2304::{one, two};
 
 const INPUT: &str = include_str!("../input");
 
-fn main() -> Result<()> {
+fn main() -> Result<(), parse::Report> {
     let score = one::calculate_score(INPUT).with_context(|| "calculating score for input")?;
     let count = two::total_scratchcards(INPUT).with_context(|| "counting total scratchcards")?;
 
@@ -1,8 +1,10 @@
 use crate::scratchcards::Table;
 use anyhow::Result;
+use registry::aoc;
 
+#[aoc(year = 2023, day = 4, part = 1)]
 pub fn calculate_score(s: &str) -> Result<usize> {
-    Ok(Table::parse(s)?.score())
+    Table::parse(s)?.score()
 }
 
 #[cfg(test)]
@@ -1,62 +1,146 @@
-use std::{collections::HashMap, num::ParseIntError};
+use std::fmt::Write;
 
 use anyhow::{Context, Result};
+use parse::{AocParse, FromSpan};
 
+/// Cards ordered by id, each with the number of copies held before any are won.
+///
+/// A repeated id adds a copy to the existing entry rather than a second card,
+/// and copies won for ids missing from the table are discarded.
 #[derive(Debug, Default)]
 pub struct Table {
-    cards: HashMap<usize, (Card, usize)>,
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    card: Card,
+    matches: usize,
+    copies: usize,
 }
 
 impl Table {
     pub fn parse(s: &str) -> Result<Table> {
         let mut table = Table::default();
 
-        for line in s.lines() {
+        for line in parse::lines(s) {
             if line.is_empty() {
                 continue;
             }
-            table.add_card(&Card::parse(line)?)
+            table.add_card(Card::from_span(&line)?)
         }
 
         Ok(table)
     }
 
-    fn add_card(&mut self, card: &Card) {
-        self.cards
-            .entry(card.id)
-            .and_modify(|(_, c)| *c += 1)
-            .or_insert((card.clone(), 1));
+    fn add_card(&mut self, card: Card) {
+        match self.entries.binary_search_by_key(&card.id, |e| e.card.id) {
+            Ok(idx) => self.entries[idx].copies += 1,
+            Err(idx) => {
+                let matches = card.winning_count();
+                let entry = Entry {
+                    card,
+                    matches,
+                    copies: 1,
+                };
+                self.entries.insert(idx, entry)
+            }
+        }
     }
 
     pub fn count_scratchcards(&self) -> usize {
-        let mut total_count = 0;
+        let mut copies: Vec<usize> = self.entries.iter().map(|e| e.copies).collect();
 
-        let mut cards = self.cards.clone();
+        for (idx, entry) in self.entries.iter().enumerate() {
+            // ids stop at usize::MAX, so nothing past it can be won
+            let last_won = entry.card.id.saturating_add(entry.matches);
+            for next in idx + 1..self.entries.len() {
+                if self.entries[next].card.id > last_won {
+                    break;
+                }
+                copies[next] += copies[idx];
+            }
+        }
 
-        let mut keys: Vec<&usize> = self.cards.keys().collect();
-        keys.sort_unstable();
+        copies.iter().sum()
+    }
 
-        for id in keys {
-            let (card, count) = cards.get(id).unwrap().clone();
+    /// Breaks the count down per card in id order, recording which earlier
+    /// cards each one was won from.
+    pub fn cascade(&self) -> Vec<CardTrace> {
+        let mut traces: Vec<CardTrace> = self
+            .entries
+            .iter()
+            .map(|e| CardTrace {
+                id: e.card.id,
+                matches: e.matches,
+                original: e.copies,
+                received: vec![],
+                count: e.copies,
+            })
+            .collect();
 
-            total_count += count;
+        for idx in 0..traces.len() {
+            let (done, rest) = traces.split_at_mut(idx + 1);
+            let trace = &done[idx];
+            let last_won = trace.id.saturating_add(trace.matches);
+            for next in rest.iter_mut().take_while(|t| t.id <= last_won) {
+                next.received.push((trace.id, trace.count));
+                next.count += trace.count;
+            }
+        }
+
+        traces
+    }
+
+    /// Renders the cascade as a Graphviz DOT graph, with an edge for every
+    /// card that won copies of another labelled with the number of copies.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph scratchcards {\n");
 
-            for i in 1..=card.winning_count() {
-                cards.entry(id + i).and_modify(|(_, c)| *c += count);
+        let traces = self.cascade();
+        for trace in &traces {
+            let _ = writeln!(
+                dot,
+                "    card{0} [label=\"Card {0}\\nmatches: {1}\\ncount: {2}\"];",
+                trace.id, trace.matches, trace.count
+            );
+        }
+        for trace in &traces {
+            for (from, copies) in &trace.received {
+                let _ = writeln!(
+                    dot,
+                    "    card{from} -> card{} [label=\"{copies}\"];",
+                    trace.id
+                );
             }
         }
 
-        total_count
+        dot.push_str("}\n");
+        dot
     }
 
-    pub fn score(&self) -> usize {
-        self.cards
-            .iter()
-            .fold(0, |acc, (_, (card, _))| acc + card.score())
+    pub fn score(&self) -> Result<usize> {
+        self.entries.iter().try_fold(0usize, |acc, e| {
+            let score =
+                score_matches(e.matches).with_context(|| format!("scoring card {}", e.card.id))?;
+            acc.checked_add(score)
+                .with_context(|| format!("adding score for card {}: total overflows", e.card.id))
+        })
     }
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CardTrace {
+    pub id: usize,
+    pub matches: usize,
+    pub original: usize,
+    pub received: Vec<(usize, usize)>,
+    pub count: usize,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, AocParse)]
+#[parse(format = "Card {id}: {winning_numbers} | {scratch_numbers}")]
 pub struct Card {
     id: usize,
     winning_numbers: Vec<usize>,
@@ -65,46 +149,45 @@ pub struct Card {
 
 impl Card {
     pub fn parse(s: &str) -> Result<Card> {
-        let mut card = Card::default();
-
-        let (id, s) = s.split_once(": ").with_context(|| "splitting prefix")?;
-
-        card.id = id
-            .strip_prefix("Card")
-            .with_context(|| "stripping prefix")?
-            .trim()
-            .parse()
-            .with_context(|| "parsing id")?;
-
-        let (winning, scratch) = s.split_once(" | ").with_context(|| "splitting numbers")?;
-
-        card.winning_numbers = parse_number_set(winning)?;
-        card.scratch_numbers = parse_number_set(scratch)?;
-
-        Ok(card)
+        Ok(s.parse()?)
     }
 
     pub fn winning_count(&self) -> usize {
-        self.winning_numbers
-            .iter()
-            .filter(|w| self.scratch_numbers.contains(w))
-            .count()
+        let mut winning = self.winning_numbers.clone();
+        let mut scratch = self.scratch_numbers.clone();
+        winning.sort_unstable();
+        scratch.sort_unstable();
+
+        let mut count = 0;
+        let mut scratch = scratch.iter().peekable();
+        for number in &winning {
+            while scratch.next_if(|s| *s < number).is_some() {}
+            if scratch.peek() == Some(&number) {
+                count += 1;
+            }
+        }
+        count
     }
 
-    pub fn score(&self) -> usize {
-        (1 << self.winning_count()) >> 1
+    pub fn score(&self) -> Result<usize> {
+        score_matches(self.winning_count())
     }
 }
 
-fn parse_number_set(s: &str) -> Result<Vec<usize>> {
-    s.split_ascii_whitespace()
-        .map(str::parse::<usize>)
-        .collect::<Result<Vec<usize>, ParseIntError>>()
-        .with_context(|| "parsing number set")
+fn score_matches(matches: usize) -> Result<usize> {
+    match matches {
+        0 => Ok(0),
+        m => u32::try_from(m - 1)
+            .ok()
+            .and_then(|shift| 1usize.checked_shl(shift))
+            .with_context(|| format!("score for {m} matches overflows")),
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use parse::testing::{is_positioned, mutated};
+    use proptest::prelude::*;
     use test_case::test_case;
 
     use super::*;
@@ -124,6 +207,129 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
         assert_eq!(table.count_scratchcards(), 30);
     }
 
+    #[test_case("Card 1: 41 48 | 83\nCard 2: 13 3x | 61", "line 2, column 12: invalid number \"3x\": invalid digit found in string" ; "bad number")]
+    #[test_case("Card 1: 41 48 83", "line 1, column 17: expected \" | \"" ; "missing separator")]
+    #[test_case("Crd 1: 41 | 83", "line 1, column 1: expected \"Card \"" ; "bad prefix")]
+    fn test_table_parse_error(input: &str, expected: &str) {
+        let err = Table::parse(input).expect_err("table should not parse");
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[test_case("Card 1: 1 2 | 1 2\nCard 2: 3 | 3\nCard 3: 4 | 5", 7 ; "in order")]
+    #[test_case("Card 3: 4 | 5\nCard 1: 1 2 | 1 2\nCard 2: 3 | 3", 7 ; "out of order")]
+    #[test_case("Card 1: 1 2 | 1 2\nCard 3: 4 | 5", 3 ; "missing id discards copies")]
+    #[test_case("Card 1: 1 | 1\nCard 1: 1 | 1\nCard 2: 3 | 4", 5 ; "duplicate id adds a copy")]
+    #[test_case("Card 1: 5 | 1\nCard 2: 3 | 4\n", 2 ; "no matches")]
+    #[test_case("Card 1: 1 2 | 1 2\nCard 2: 3 | 3", 3 ; "copies past the last card")]
+    fn test_table_count_scratchcards(input: &str, expected: usize) {
+        let table = Table::parse(input).expect("table should parse");
+        assert_eq!(table.count_scratchcards(), expected);
+    }
+
+    #[test]
+    fn test_table_huge_ids() {
+        let input = format!(
+            "Card {}: 1 2 | 1 2\nCard {}: 3 | 4",
+            usize::MAX - 1,
+            usize::MAX
+        );
+        let table = Table::parse(&input).expect("table should parse");
+        assert_eq!(table.count_scratchcards(), 3);
+        let counts: Vec<usize> = table.cascade().iter().map(|t| t.count).collect();
+        assert_eq!(counts, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_table_cascade() {
+        let input = "Card 1: 1 2 | 1 2\nCard 1: 9 | 8\nCard 2: 3 | 3\nCard 3: 4 | 5";
+        let table = Table::parse(input).expect("table should parse");
+        let expected = vec![
+            CardTrace {
+                id: 1,
+                matches: 2,
+                original: 2,
+                received: vec![],
+                count: 2,
+            },
+            CardTrace {
+                id: 2,
+                matches: 1,
+                original: 1,
+                received: vec![(1, 2)],
+                count: 3,
+            },
+            CardTrace {
+                id: 3,
+                matches: 0,
+                original: 1,
+                received: vec![(1, 2), (2, 3)],
+                count: 6,
+            },
+        ];
+        assert_eq!(table.cascade(), expected);
+    }
+
+    #[test]
+    fn test_table_to_dot() {
+        let input = "Card 1: 1 2 | 1 2\nCard 2: 3 | 3\nCard 3: 4 | 5";
+        let table = Table::parse(input).expect("table should parse");
+        let expected = r#"digraph scratchcards {
+    card1 [label="Card 1\nmatches: 2\ncount: 1"];
+    card2 [label="Card 2\nmatches: 1\ncount: 2"];
+    card3 [label="Card 3\nmatches: 0\ncount: 4"];
+    card1 -> card2 [label="1"];
+    card1 -> card3 [label="1"];
+    card2 -> card3 [label="2"];
+}
+"#;
+        assert_eq!(table.to_dot(), expected);
+    }
+
+    fn generated_card(id: usize, matches: usize) -> Card {
+        Card {
+            id,
+            winning_numbers: (0..matches).collect(),
+            scratch_numbers: (0..matches).rev().collect(),
+        }
+    }
+
+    #[test_case(1, Some(1) ; "one match")]
+    #[test_case(10, Some(512) ; "ten matches")]
+    #[test_case(usize::BITS as usize, Some(1 << (usize::BITS - 1)) ; "largest score")]
+    #[test_case(usize::BITS as usize + 1, None ; "overflowing score")]
+    #[test_case(1_000, None ; "very large winning set")]
+    fn test_card_score_large(matches: usize, expected: Option<usize>) {
+        let card = generated_card(1, matches);
+        assert_eq!(card.winning_count(), matches);
+        assert_eq!(card.score().ok(), expected);
+    }
+
+    #[test]
+    fn test_table_score_overflow() {
+        let bits = usize::BITS as usize;
+        let mut table = Table::default();
+        table.add_card(generated_card(1, bits));
+        assert_eq!(table.score().ok(), Some(1 << (bits - 1)));
+
+        table.add_card(generated_card(2, bits));
+        let err = table.score().expect_err("total should overflow");
+        assert_eq!(err.to_string(), "adding score for card 2: total overflows");
+    }
+
+    #[test_case(&[1, 2, 3], &[3, 2, 1], 3 ; "all match")]
+    #[test_case(&[5, 5], &[5], 2 ; "repeated winning number")]
+    #[test_case(&[5], &[5, 5], 1 ; "repeated scratch number")]
+    #[test_case(&[], &[1], 0 ; "no winning numbers")]
+    #[test_case(&[10, 1, 7], &[2, 7, 9, 10], 2 ; "unsorted")]
+    fn test_card_winning_count(winning: &[usize], scratch: &[usize], expected: usize) {
+        let card = Card {
+            id: 1,
+            winning_numbers: winning.to_vec(),
+            scratch_numbers: scratch.to_vec(),
+        };
+        assert_eq!(card.winning_count(), expected);
+    }
+
     #[test_case(
         "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
         Card{
@@ -187,6 +393,38 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
     fn test_card_parse(input: &str, expected_card: Card, expected_score: usize) {
         let card = Card::parse(input).expect("card should parse");
         assert_eq!(card, expected_card, "card not equal to expected");
-        assert_eq!(card.score(), expected_score, "score not equal to expected")
+        let score = card.score().expect("card should score");
+        assert_eq!(score, expected_score, "score not equal to expected")
+    }
+
+    fn tables() -> impl Strategy<Value = String> {
+        let numbers = || prop::collection::vec(1..100usize, 1..8);
+        prop::collection::vec((numbers(), numbers()), 0..10).prop_map(|cards| {
+            let join = |numbers: &[usize]| {
+                let numbers: Vec<String> = numbers.iter().map(|n| format!("{n:>2}")).collect();
+                numbers.join(" ")
+            };
+            cards
+                .iter()
+                .enumerate()
+                .map(|(idx, (winning, scratch))| {
+                    format!("Card {}: {} | {}\n", idx + 1, join(winning), join(scratch))
+                })
+                .collect()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_table_parse_valid(input in tables()) {
+            prop_assert!(Table::parse(&input).is_ok());
+        }
+
+        #[test]
+        fn test_table_parse_mutated(input in mutated(tables())) {
+            if let Err(err) = Table::parse(&input) {
+                prop_assert!(is_positioned(err.as_ref()), "{err:#}");
+            }
+        }
     }
 }
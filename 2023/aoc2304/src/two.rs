@@ -1,6 +1,8 @@
 use crate::scratchcards::Table;
 use anyhow::Result;
+use registry::aoc;
 
+#[aoc(year = 2023, day = 4, part = 2)]
 pub fn total_scratchcards(s: &str) -> Result<usize> {
     Ok(Table::parse(s)?.count_scratchcards())
 }
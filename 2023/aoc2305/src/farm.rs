@@ -1,5 +1,6 @@
-use anyhow::{bail, Context, Result};
-use std::{cmp::Ordering, iter::Peekable, num::ParseIntError, ops::Range, str::Lines};
+use anyhow::{Context, Result};
+use parse::Span;
+use std::{cmp::Ordering, ops::Range};
 #[cfg(concurrency)]
 use std::{sync::mpsc::channel, sync::Arc, thread::spawn};
 
@@ -16,12 +17,19 @@ impl RangeMap {
         RangeMap { source, shift }
     }
 
-    fn parse(s: &str) -> Result<RangeMap> {
-        let values = parse_isize_vec(s)?;
-        if values.len() != 3 {
-            bail!("expected 3 values per mapper line")
+    fn parse(line: &Span) -> Result<RangeMap, parse::Error> {
+        let [dst_start, src_start, length] = line.numbers::<isize>()?[..] else {
+            return Err(line.error("expected 3 values per mapper line"));
+        };
+
+        // every mapped value lies between the range starts and their ends
+        let fits = dst_start.checked_add(length).is_some()
+            && src_start.checked_add(length).is_some()
+            && dst_start.checked_sub(src_start).is_some();
+        match fits {
+            true => Ok(RangeMap::new(dst_start, src_start, length)),
+            false => Err(line.error("mapper range overflows")),
         }
-        Ok(RangeMap::new(values[0], values[1], values[2]))
     }
 
     fn map_value(&self, item: &isize) -> Option<isize> {
@@ -68,54 +76,31 @@ pub struct Almanac {
 
 impl Almanac {
     pub fn with_seed_list(s: &str) -> Result<Almanac> {
-        Almanac::parse(s, parse_isize_vec)
+        Almanac::parse(s, parse_isize_list)
     }
 
     pub fn with_seed_ranges(s: &str) -> Result<Almanac> {
         Almanac::parse(s, parse_isize_range_seq)
     }
 
-    fn parse(s: &str, seed_parser: fn(&str) -> Result<Vec<isize>>) -> Result<Almanac> {
-        println!("starting almanac parse");
+    fn parse(s: &str, seed_parser: SeedParser) -> Result<Almanac> {
         let mut almanac = Almanac::default();
-        let mut lines = s.lines().peekable();
-        while let Some(line) = lines.next() {
-            match line {
-                l if l.starts_with("seeds: ") => {
-                    println!("found seeds");
-                    let (_, seeds) = l.split_once(": ").unwrap();
-                    almanac.seeds = seed_parser(seeds).with_context(|| "parsing seeds")?;
+        for block in parse::blocks(s) {
+            let mappers = match block.header.as_str() {
+                "seeds" => {
+                    almanac.seeds = seed_parser(&block.values).with_context(|| "parsing seeds")?;
+                    continue;
                 }
-                "seed-to-soil map:" => {
-                    println!("found seed to soil map");
-                    almanac.seed_soil = parse_mappers(&mut lines)?;
-                }
-                "soil-to-fertilizer map:" => {
-                    println!("found soil to fertilizer map");
-                    almanac.soil_fertilizer = parse_mappers(&mut lines)?;
-                }
-                "fertilizer-to-water map:" => {
-                    println!("found fertilizer to water map");
-                    almanac.fertilizer_water = parse_mappers(&mut lines)?;
-                }
-                "water-to-light map:" => {
-                    println!("found water to light map");
-                    almanac.water_light = parse_mappers(&mut lines)?;
-                }
-                "light-to-temperature map:" => {
-                    println!("found light to temperature map");
-                    almanac.light_temperature = parse_mappers(&mut lines)?;
-                }
-                "temperature-to-humidity map:" => {
-                    println!("found temperature to humidity map");
-                    almanac.temperature_humidity = parse_mappers(&mut lines)?;
-                }
-                "humidity-to-location map:" => {
-                    println!("found humidity to location map");
-                    almanac.humidity_location = parse_mappers(&mut lines)?;
-                }
-                _ => {}
-            }
+                "seed-to-soil map" => &mut almanac.seed_soil,
+                "soil-to-fertilizer map" => &mut almanac.soil_fertilizer,
+                "fertilizer-to-water map" => &mut almanac.fertilizer_water,
+                "water-to-light map" => &mut almanac.water_light,
+                "light-to-temperature map" => &mut almanac.light_temperature,
+                "temperature-to-humidity map" => &mut almanac.temperature_humidity,
+                "humidity-to-location map" => &mut almanac.humidity_location,
+                _ => continue,
+            };
+            *mappers = parse_mappers(&block.body)?;
         }
         Ok(almanac)
     }
@@ -126,11 +111,8 @@ impl Almanac {
 
         let almanac = Arc::new(self.clone());
 
-        println!("Seed count: {}", self.seeds.len());
-
-        for (count, seed) in self.seeds.iter().enumerate() {
+        for seed in self.seeds.iter() {
             let tx = tx.clone();
-            let count = count.to_owned();
             let seed = seed.to_owned();
             let almanac = Arc::clone(&almanac);
             spawn(move || -> Result<()> {
@@ -142,7 +124,6 @@ impl Almanac {
                 let hum = with_mappers(&almanac.temperature_humidity, &temp);
                 let loc = with_mappers(&almanac.humidity_location, &hum);
                 tx.send(loc).with_context(|| "transmitting thread data")?;
-                println!("thread {} complete", count);
                 Ok(())
             });
         }
@@ -194,30 +175,32 @@ fn with_mappers(maps: &[RangeMap], value: &isize) -> isize {
         .unwrap_or(*value)
 }
 
-fn parse_isize_vec(s: &str) -> Result<Vec<isize>, anyhow::Error> {
-    s.split_ascii_whitespace()
-        .map(str::parse::<isize>)
-        .collect::<Result<Vec<isize>, ParseIntError>>()
-        .with_context(|| "parsing isize vec from string")
+type SeedParser = fn(&Span) -> Result<Vec<isize>, parse::Error>;
+
+fn parse_isize_list(s: &Span) -> Result<Vec<isize>, parse::Error> {
+    s.numbers()
 }
 
-fn parse_isize_range_seq(s: &str) -> Result<Vec<isize>, anyhow::Error> {
-    let split = parse_isize_vec(s)?;
+fn parse_isize_range_seq(s: &Span) -> Result<Vec<isize>, parse::Error> {
+    let split = parse_isize_list(s)?;
     if split.len() % 2 != 0 {
-        bail!("invalid seed range sequence")
+        return Err(s.error("invalid seed range sequence"));
+    }
+    let mut seeds = vec![];
+    for chunk in split.chunks_exact(2) {
+        let end = chunk[0]
+            .checked_add(chunk[1])
+            .ok_or_else(|| s.error("seed range overflows"))?;
+        seeds.extend(chunk[0]..end);
     }
-    let seeds = split
-        .chunks_exact(2)
-        .flat_map(|chunk| chunk[0]..(chunk[0] + chunk[1]))
-        .collect();
     Ok(seeds)
 }
 
-fn parse_mappers(lines: &mut Peekable<Lines<'_>>) -> Result<Vec<RangeMap>> {
-    let mut mappers = vec![];
-    while let Some(line) = lines.next_if(|l| !l.is_empty()) {
-        mappers.push(RangeMap::parse(line)?);
-    }
+fn parse_mappers(lines: &[Span]) -> Result<Vec<RangeMap>, parse::Error> {
+    let mut mappers = lines
+        .iter()
+        .map(RangeMap::parse)
+        .collect::<Result<Vec<_>, _>>()?;
     mappers.sort_unstable();
     Ok(mappers)
 }
@@ -227,6 +210,8 @@ mod tests {
     use super::*;
 
     use anyhow::Result;
+    use parse::testing::{is_positioned, mutated};
+    use proptest::prelude::*;
 
     const INPUT: &str = "
 seeds: 79 14 55 13
@@ -278,4 +263,68 @@ humidity-to-location map:
         assert_eq!(Almanac::with_seed_ranges(INPUT)?.closest(), expected);
         Ok(())
     }
+
+    #[test]
+    fn test_almanac_parse_error_position() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50\n";
+        let err = Almanac::with_seed_list(input).expect_err("almanac should not parse");
+        assert_eq!(
+            err.to_string(),
+            "line 5, column 1: expected 3 values per mapper line"
+        );
+    }
+
+    #[test]
+    fn test_almanac_mapper_overflow() {
+        let input = "seeds: 1\n\nseed-to-soil map:\n9223372036854775807 0 1\n";
+        let err = Almanac::with_seed_list(input).expect_err("almanac should not parse");
+        assert_eq!(err.to_string(), "line 4, column 1: mapper range overflows");
+    }
+
+    const MAPS: &[&str] = &[
+        "seed-to-soil",
+        "soil-to-fertilizer",
+        "fertilizer-to-water",
+        "water-to-light",
+        "light-to-temperature",
+        "temperature-to-humidity",
+        "humidity-to-location",
+    ];
+
+    fn almanacs() -> impl Strategy<Value = String> {
+        let seeds = prop::collection::vec((0..100isize, 1..10isize), 1..4);
+        let mapper = (0..100isize, 0..100isize, 1..20isize);
+        let maps = prop::collection::vec(prop::collection::vec(mapper, 0..4), MAPS.len());
+        (seeds, maps).prop_map(|(seeds, maps)| {
+            let seeds: Vec<String> = seeds
+                .iter()
+                .flat_map(|(start, len)| [start.to_string(), len.to_string()])
+                .collect();
+            let mut almanac = format!("seeds: {}\n", seeds.join(" "));
+            for (name, mappers) in MAPS.iter().zip(maps) {
+                almanac.push_str(&format!("\n{name} map:\n"));
+                for (dst, src, len) in mappers {
+                    almanac.push_str(&format!("{dst} {src} {len}\n"));
+                }
+            }
+            almanac
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_almanac_parse_valid(input in almanacs()) {
+            prop_assert!(Almanac::with_seed_list(&input).is_ok());
+            prop_assert!(Almanac::with_seed_ranges(&input).is_ok());
+        }
+
+        // seed ranges are expanded in full, so a mutated length could ask for
+        // billions of seeds; only the seed list is checked against mutations
+        #[test]
+        fn test_almanac_parse_mutated(input in mutated(almanacs())) {
+            if let Err(err) = Almanac::with_seed_list(&input) {
+                prop_assert!(is_positioned(err.as_ref()), "{err:#}");
+            }
+        }
+    }
 }
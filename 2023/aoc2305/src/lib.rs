@@ -1,4 +1,3 @@
+pub mod farm;
 pub mod one;
 pub mod two;
-
-mod farm;
@@ -1,7 +1,9 @@
 use anyhow::{Context, Result};
+use registry::aoc;
 
 use crate::farm::Almanac;
 
+#[aoc(year = 2023, day = 5, part = 2)]
 pub fn closest_sequence_location(s: &str) -> Result<isize> {
     Almanac::with_seed_ranges(s)?
         .closest()
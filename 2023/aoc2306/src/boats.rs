@@ -1,6 +1,5 @@
-use std::num::ParseIntError;
-
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use parse::Span;
 
 #[derive(Debug, Default)]
 pub struct RaceList {
@@ -13,56 +12,11 @@ impl RaceList {
     }
 
     pub fn parse_sequence(s: &str) -> Result<RaceList> {
-        let mut lines = s.lines();
-        let times = lines
-            .next()
-            .with_context(|| "getting first line")?
-            .strip_prefix("Time:")
-            .with_context(|| "stripping time prefix")?
-            .split_ascii_whitespace()
-            .map(str::parse::<usize>)
-            .collect::<Result<Vec<usize>, ParseIntError>>()
-            .with_context(|| "parsing times")?;
-        let distances = lines
-            .next()
-            .with_context(|| "getting second line")?
-            .strip_prefix("Distance:")
-            .with_context(|| "stripping distance prefix")?
-            .split_ascii_whitespace()
-            .map(str::parse::<usize>)
-            .collect::<Result<Vec<usize>, ParseIntError>>()
-            .with_context(|| "parsing distances")?;
-
-        let races = times
-            .into_iter()
-            .zip(distances)
-            .map(|(time, distance)| Race::new(time, distance))
-            .collect();
-
-        Ok(RaceList::new(races))
+        Ok(RaceList::new(RaceSheet::parse(s)?.races()))
     }
 
     pub fn parse_single(s: &str) -> Result<RaceList> {
-        let mut lines = s.lines();
-
-        let time = lines
-            .next()
-            .with_context(|| "getting first line")?
-            .strip_prefix("Time:")
-            .with_context(|| "stripping time prefix")?
-            .replace(' ', "")
-            .parse()
-            .with_context(|| "parsing time")?;
-        let distance = lines
-            .next()
-            .with_context(|| "getting second line")?
-            .strip_prefix("Distance:")
-            .with_context(|| "stripping distance prefix")?
-            .replace(' ', "")
-            .parse()
-            .with_context(|| "parsing distance")?;
-
-        Ok(RaceList::new(vec![Race::new(time, distance)]))
+        Ok(RaceList::new(vec![RaceSheet::parse(s)?.kerned()?]))
     }
 
     pub fn win_score(&self) -> usize {
@@ -74,8 +28,85 @@ impl RaceList {
     }
 }
 
-#[derive(Debug, Default)]
-struct Race {
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RaceSheet {
+    times: Vec<Cell>,
+    distances: Vec<Cell>,
+}
+
+impl RaceSheet {
+    pub fn parse(s: &str) -> Result<RaceSheet> {
+        let mut lines = parse::lines(s);
+
+        let times = lines.next().with_context(|| "line 1: missing time row")?;
+        let times = Cell::parse_row(&times, "Time")?;
+
+        let distances = lines
+            .next()
+            .with_context(|| "line 2: missing distance row")?;
+        let distances = Cell::parse_row(&distances, "Distance")?;
+
+        if times.len() != distances.len() {
+            bail!(
+                "line 2: found {} distances for {} times",
+                distances.len(),
+                times.len()
+            )
+        }
+
+        Ok(RaceSheet { times, distances })
+    }
+
+    pub fn races(&self) -> Vec<Race> {
+        self.times
+            .iter()
+            .zip(&self.distances)
+            .map(|(time, distance)| Race::new(time.value, distance.value))
+            .collect()
+    }
+
+    pub fn kerned(&self) -> Result<Race> {
+        let time = Cell::kern(&self.times).with_context(|| "line 1: kerning times")?;
+        let distance = Cell::kern(&self.distances).with_context(|| "line 2: kerning distances")?;
+        Ok(Race::new(time, distance))
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Cell {
+    digits: String,
+    value: usize,
+}
+
+impl Cell {
+    fn parse_row(line: &Span, label: &str) -> Result<Vec<Cell>, parse::Error> {
+        let mut cells = vec![];
+        for token in line.expect_label(label)?.tokens() {
+            let digits = token.as_str();
+            if !digits.chars().all(|c| c.is_ascii_digit()) {
+                return Err(token.error(format!("expected digits, found {digits:?}")));
+            }
+
+            let value = token.number()?;
+            let digits = digits.to_string();
+            cells.push(Cell { digits, value });
+        }
+
+        Ok(cells)
+    }
+
+    fn kern(cells: &[Cell]) -> Result<usize> {
+        if cells.is_empty() {
+            bail!("no values to kern")
+        }
+
+        let digits: String = cells.iter().map(|c| c.digits.as_str()).collect();
+        Ok(digits.parse()?)
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Race {
     time: usize,
     distance: usize,
 }
@@ -86,27 +117,123 @@ impl Race {
     }
 
     fn possible_wins(&self) -> usize {
-        (1..self.time)
-            .filter(|t| {
-                let speed = t;
-                let time = self.time - t;
-                let distance = speed * time;
-                distance > self.distance
-            })
-            .count()
+        let time = self.time as u128;
+        let distance = self.distance as u128;
+
+        // the best hold time is half the race, if that can't win nothing can
+        let half = time / 2;
+        if half * (time - half) <= distance {
+            return 0;
+        }
+
+        // wins are the hold times strictly between the roots of h * (t - h) = d,
+        // isqrt rounds down so the estimate is at most one step below the first win
+        let root = (time * time - 4 * distance).isqrt();
+        let mut first = (time - root) / 2;
+        while first * (time - first) <= distance {
+            first += 1;
+        }
+
+        (time - 2 * first + 1) as usize
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use parse::testing::{is_positioned, mutated};
+    use proptest::prelude::*;
     use test_case::test_case;
 
     use super::*;
 
+    fn brute_force_wins(race: &Race) -> usize {
+        let time = race.time as u128;
+        let distance = race.distance as u128;
+        (1..time).filter(|t| t * (time - t) > distance).count()
+    }
+
     #[test_case(Race::new(7, 9), 4)]
     #[test_case(Race::new(15, 40), 8)]
     #[test_case(Race::new(30, 200), 9)]
+    #[test_case(Race::new(71530, 940200), 71503)]
+    #[test_case(Race::new(0, 0), 0 ; "no time")]
+    #[test_case(Race::new(10, 25), 0 ; "perfect square tie")]
+    #[test_case(Race::new(10, 24), 1 ; "perfect square single win")]
+    #[test_case(Race::new(11, 30), 0 ; "odd time tie")]
+    #[test_case(Race::new(usize::MAX, usize::MAX), usize::MAX - 3 ; "large values")]
     fn test_race_possible_wins(race: Race, expected: usize) {
         assert_eq!(race.possible_wins(), expected)
     }
+
+    const EXAMPLE: &str = "
+Time:      7  15   30
+Distance:  9  40  200
+";
+
+    #[test]
+    fn test_race_sheet_races() -> Result<()> {
+        let sheet = RaceSheet::parse(EXAMPLE.trim_start())?;
+        let expected = vec![Race::new(7, 9), Race::new(15, 40), Race::new(30, 200)];
+        assert_eq!(sheet.races(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_race_sheet_kerned() -> Result<()> {
+        let sheet = RaceSheet::parse(EXAMPLE.trim_start())?;
+        assert_eq!(sheet.kerned()?, Race::new(71530, 940200));
+        Ok(())
+    }
+
+    #[test_case("Time: 7\nDistance: 9 40", "line 2: found 2 distances for 1 times" ; "unequal rows")]
+    #[test_case("Time: 7", "line 2: missing distance row" ; "missing distance row")]
+    #[test_case("Tme: 7\nDistance: 9", "line 1, column 1: expected label \"Time\"" ; "bad time prefix")]
+    #[test_case("Time: 7\nDistance: 9 4x", "line 2, column 13: expected digits, found \"4x\"" ; "bad distance")]
+    #[test_case("Time:  7 -15\nDistance: 9 40", "line 1, column 10: expected digits, found \"-15\"" ; "negative time")]
+    #[test_case("Time: 99999999999999999999\nDistance: 9", "line 1, column 7: invalid number \"99999999999999999999\": number too large to fit in target type" ; "too large")]
+    fn test_race_sheet_parse_error(input: &str, expected: &str) {
+        let err = RaceSheet::parse(input).expect_err("sheet should not parse");
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[test]
+    fn test_race_sheet_kerned_overflow() -> Result<()> {
+        let sheet = RaceSheet::parse("Time: 99999999999 99999999999\nDistance: 1 2")?;
+        let err = sheet.kerned().expect_err("kerned time should overflow");
+        assert_eq!(err.to_string(), "line 1: kerning times");
+        Ok(())
+    }
+
+    fn sheets() -> impl Strategy<Value = String> {
+        prop::collection::vec((0..100usize, 0..2000usize), 1..5).prop_map(|races| {
+            let times: Vec<String> = races.iter().map(|(t, _)| format!("{t:>4}")).collect();
+            let distances: Vec<String> = races.iter().map(|(_, d)| format!("{d:>4}")).collect();
+            format!(
+                "Time:    {}\nDistance:{}\n",
+                times.join(" "),
+                distances.join(" ")
+            )
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_race_sheet_parse_valid(input in sheets()) {
+            prop_assert!(RaceSheet::parse(&input).is_ok());
+        }
+
+        #[test]
+        fn test_race_sheet_parse_mutated(input in mutated(sheets())) {
+            if let Err(err) = RaceSheet::parse(&input) {
+                prop_assert!(is_positioned(err.as_ref()), "{err:#}");
+            }
+        }
+
+        #[test]
+        fn test_race_possible_wins_matches_brute_force(time in 0..2_000usize, ratio in 0.0..1.1f64) {
+            let best = (time / 2) * (time - time / 2);
+            let race = Race::new(time, (best as f64 * ratio) as usize);
+            prop_assert_eq!(race.possible_wins(), brute_force_wins(&race));
+        }
+    }
 }
@@ -1,4 +1,3 @@
+pub mod boats;
 pub mod one;
 pub mod two;
-
-mod boats;
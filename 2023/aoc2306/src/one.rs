@@ -1,6 +1,8 @@
 use crate::boats::RaceList;
 use anyhow::Result;
+use registry::aoc;
 
+#[aoc(year = 2023, day = 6, part = 1)]
 pub fn race_result(s: &str) -> Result<usize> {
     Ok(RaceList::parse_sequence(s)?.win_score())
 }
@@ -1,7 +1,9 @@
 use anyhow::Result;
+use registry::aoc;
 
 use crate::boats::RaceList;
 
+#[aoc(year = 2023, day = 6, part = 2)]
 pub fn race_result(s: &str) -> Result<usize> {
     Ok(RaceList::parse_single(s)?.win_score())
 }
@@ -1,4 +1,3 @@
 pub mod one;
+pub mod poker;
 pub mod two;
-
-mod poker;
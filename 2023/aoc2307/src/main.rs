@@ -1,9 +1,8 @@
-use anyhow::Result;
 use aoc2307::{one, two};
 
 const INPUT: &str = include_str!("../input");
 
-fn main() -> Result<()> {
+fn main() -> Result<(), parse::Report> {
     let winnings_one = one::winnings(INPUT)?;
     let winnings_two = two::winnings(INPUT)?;
 
@@ -1,35 +1,47 @@
 use std::{cmp::Ordering, collections::HashMap};
 
 use anyhow::{bail, Context, Result};
+use parse::Span;
 
 pub fn play_without_joker(s: &str) -> Result<usize> {
-    parse(s, false)
+    Game::parse(s, false)?.winnings()
 }
 
 pub fn play_with_joker(s: &str) -> Result<usize> {
-    parse(s, true)
+    Game::parse(s, true)?.winnings()
 }
 
-fn parse(s: &str, with_joker: bool) -> Result<usize> {
-    let mut sets = s
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(|s| Set::parse(s, with_joker))
-        .collect::<Result<Vec<Set>>>()
-        .with_context(|| "parsing sets")?;
-    sets.sort_unstable();
-
-    let winnings = sets
-        .iter()
-        .enumerate()
-        .map(|(i, set)| {
-            let rank = sets.len() - i;
-            set.bid * rank
-        })
-        .reduce(|a, b| a + b)
-        .with_context(|| "totalling winnings")?;
+/// Every hand dealt along with its bid, strongest hand first.
+#[derive(Debug, Default)]
+pub struct Game {
+    sets: Vec<Set>,
+}
+
+impl Game {
+    pub fn parse(s: &str, with_joker: bool) -> Result<Game> {
+        let mut sets = parse::lines(s)
+            .filter(|l| !l.is_empty())
+            .map(|line| Set::parse(&line, with_joker))
+            .collect::<Result<Vec<Set>, parse::Error>>()
+            .with_context(|| "parsing sets")?;
+        sets.sort_unstable();
+        Ok(Game { sets })
+    }
 
-    Ok(winnings)
+    pub fn winnings(&self) -> Result<usize> {
+        let winnings = self
+            .sets
+            .iter()
+            .enumerate()
+            .map(|(i, set)| {
+                let rank = self.sets.len() - i;
+                set.bid * rank
+            })
+            .reduce(|a, b| a + b)
+            .with_context(|| "totalling winnings")?;
+
+        Ok(winnings)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -51,10 +63,10 @@ impl Ord for Set {
 }
 
 impl Set {
-    fn parse(s: &str, with_joker: bool) -> Result<Set> {
-        let (hand, bid) = s.split_once(' ').with_context(|| "splitting line")?;
-        let hand = Hand::parse(hand, with_joker).with_context(|| "parsing hand")?;
-        let bid = bid.parse().with_context(|| "parsing bid")?;
+    fn parse(line: &Span, with_joker: bool) -> Result<Set, parse::Error> {
+        let (hand, bid) = line.split_once(" ")?;
+        let hand = Hand::from_span(&hand, with_joker)?;
+        let bid = bid.number()?;
         Ok(Set { hand, bid })
     }
 }
@@ -81,18 +93,20 @@ impl Ord for Hand {
 }
 
 impl Hand {
-    fn parse(s: &str, with_joker: bool) -> Result<Hand> {
-        if s.len() != 5 {
-            bail!("hand length must be equal to 5: {s}");
+    fn from_span(span: &Span, with_joker: bool) -> Result<Hand, parse::Error> {
+        let labels = span.chars();
+        if labels.len() != 5 {
+            return Err(span.error(format!("expected 5 cards, found {}", labels.len())));
         }
 
         let mut cards: [Card; 5] = Default::default();
 
-        for (idx, label) in s.char_indices() {
-            cards[idx] = match with_joker {
-                true => Card::with_joker(label)?,
-                false => Card::without_joker(label)?,
+        for (card, (label, at)) in cards.iter_mut().zip(labels) {
+            let parsed = match with_joker {
+                true => Card::with_joker(label),
+                false => Card::without_joker(label),
             };
+            *card = parsed.map_err(|err| at.error(err.to_string()))?;
         }
 
         let hand_type = match with_joker {
@@ -247,6 +261,10 @@ impl Type {
 
 #[cfg(test)]
 mod tests {
+    use parse::testing::{is_positioned, mutated};
+    use proptest::prelude::*;
+    use test_case::test_case;
+
     use super::*;
 
     #[test]
@@ -327,49 +345,58 @@ JJJJ2 41
         assert_eq!(play_with_joker(input).unwrap(), expected);
     }
 
+    #[test_case("32T3K 765\nT55X5 684", "line 2, column 4: invalid label: X" ; "bad label")]
+    #[test_case("32T3K 765\nT55J 684", "line 2, column 1: expected 5 cards, found 4" ; "short hand")]
+    #[test_case("32T3K 76x", "line 1, column 7: invalid number \"76x\": invalid digit found in string" ; "bad bid")]
+    #[test_case("32T3K765", "line 1, column 9: expected \" \"" ; "missing bid")]
+    fn test_play_parse_error(input: &str, expected: &str) {
+        let err = play_without_joker(input).expect_err("sets should not parse");
+        assert_eq!(format!("{err:#}"), format!("parsing sets: {expected}"));
+    }
+
     #[test]
     fn test_set_ord() -> Result<()> {
         let mut input = [
             Set {
-                hand: Hand::parse("32T3K", false)?,
+                hand: Hand::from_span(&Span::new("32T3K", 1), false)?,
                 bid: 765,
             },
             Set {
-                hand: Hand::parse("T55J5", false)?,
+                hand: Hand::from_span(&Span::new("T55J5", 1), false)?,
                 bid: 684,
             },
             Set {
-                hand: Hand::parse("KK677", false)?,
+                hand: Hand::from_span(&Span::new("KK677", 1), false)?,
                 bid: 28,
             },
             Set {
-                hand: Hand::parse("KTJJT", false)?,
+                hand: Hand::from_span(&Span::new("KTJJT", 1), false)?,
                 bid: 220,
             },
             Set {
-                hand: Hand::parse("QQQJA", false)?,
+                hand: Hand::from_span(&Span::new("QQQJA", 1), false)?,
                 bid: 483,
             },
         ];
         let expected = [
             Set {
-                hand: Hand::parse("QQQJA", false)?,
+                hand: Hand::from_span(&Span::new("QQQJA", 1), false)?,
                 bid: 483,
             },
             Set {
-                hand: Hand::parse("T55J5", false)?,
+                hand: Hand::from_span(&Span::new("T55J5", 1), false)?,
                 bid: 684,
             },
             Set {
-                hand: Hand::parse("KK677", false)?,
+                hand: Hand::from_span(&Span::new("KK677", 1), false)?,
                 bid: 28,
             },
             Set {
-                hand: Hand::parse("KTJJT", false)?,
+                hand: Hand::from_span(&Span::new("KTJJT", 1), false)?,
                 bid: 220,
             },
             Set {
-                hand: Hand::parse("32T3K", false)?,
+                hand: Hand::from_span(&Span::new("32T3K", 1), false)?,
                 bid: 765,
             },
         ];
@@ -381,18 +408,18 @@ JJJJ2 41
     #[test]
     fn test_hand_ord() -> Result<()> {
         let mut input = [
-            Hand::parse("32T3K", false)?,
-            Hand::parse("T55J5", false)?,
-            Hand::parse("KK677", false)?,
-            Hand::parse("KTJJT", false)?,
-            Hand::parse("QQQJA", false)?,
+            Hand::from_span(&Span::new("32T3K", 1), false)?,
+            Hand::from_span(&Span::new("T55J5", 1), false)?,
+            Hand::from_span(&Span::new("KK677", 1), false)?,
+            Hand::from_span(&Span::new("KTJJT", 1), false)?,
+            Hand::from_span(&Span::new("QQQJA", 1), false)?,
         ];
         let expected = [
-            Hand::parse("QQQJA", false)?,
-            Hand::parse("T55J5", false)?,
-            Hand::parse("KK677", false)?,
-            Hand::parse("KTJJT", false)?,
-            Hand::parse("32T3K", false)?,
+            Hand::from_span(&Span::new("QQQJA", 1), false)?,
+            Hand::from_span(&Span::new("T55J5", 1), false)?,
+            Hand::from_span(&Span::new("KK677", 1), false)?,
+            Hand::from_span(&Span::new("KTJJT", 1), false)?,
+            Hand::from_span(&Span::new("32T3K", 1), false)?,
         ];
         input.sort_unstable();
         assert_eq!(input, expected);
@@ -402,36 +429,36 @@ JJJJ2 41
     #[test]
     fn test_hand_ord_2() -> Result<()> {
         let mut input = [
-            Hand::parse("23456", false)?,
-            Hand::parse("AKQJT", false)?,
-            Hand::parse("22345", false)?,
-            Hand::parse("AAKQJ", false)?,
-            Hand::parse("22334", false)?,
-            Hand::parse("AAKKQ", false)?,
-            Hand::parse("22234", false)?,
-            Hand::parse("AAAKQ", false)?,
-            Hand::parse("22233", false)?,
-            Hand::parse("AAAKK", false)?,
-            Hand::parse("22223", false)?,
-            Hand::parse("AAAAK", false)?,
-            Hand::parse("22222", false)?,
-            Hand::parse("AAAAA", false)?,
+            Hand::from_span(&Span::new("23456", 1), false)?,
+            Hand::from_span(&Span::new("AKQJT", 1), false)?,
+            Hand::from_span(&Span::new("22345", 1), false)?,
+            Hand::from_span(&Span::new("AAKQJ", 1), false)?,
+            Hand::from_span(&Span::new("22334", 1), false)?,
+            Hand::from_span(&Span::new("AAKKQ", 1), false)?,
+            Hand::from_span(&Span::new("22234", 1), false)?,
+            Hand::from_span(&Span::new("AAAKQ", 1), false)?,
+            Hand::from_span(&Span::new("22233", 1), false)?,
+            Hand::from_span(&Span::new("AAAKK", 1), false)?,
+            Hand::from_span(&Span::new("22223", 1), false)?,
+            Hand::from_span(&Span::new("AAAAK", 1), false)?,
+            Hand::from_span(&Span::new("22222", 1), false)?,
+            Hand::from_span(&Span::new("AAAAA", 1), false)?,
         ];
         let expected = [
-            Hand::parse("AAAAA", false)?,
-            Hand::parse("22222", false)?,
-            Hand::parse("AAAAK", false)?,
-            Hand::parse("22223", false)?,
-            Hand::parse("AAAKK", false)?,
-            Hand::parse("22233", false)?,
-            Hand::parse("AAAKQ", false)?,
-            Hand::parse("22234", false)?,
-            Hand::parse("AAKKQ", false)?,
-            Hand::parse("22334", false)?,
-            Hand::parse("AAKQJ", false)?,
-            Hand::parse("22345", false)?,
-            Hand::parse("AKQJT", false)?,
-            Hand::parse("23456", false)?,
+            Hand::from_span(&Span::new("AAAAA", 1), false)?,
+            Hand::from_span(&Span::new("22222", 1), false)?,
+            Hand::from_span(&Span::new("AAAAK", 1), false)?,
+            Hand::from_span(&Span::new("22223", 1), false)?,
+            Hand::from_span(&Span::new("AAAKK", 1), false)?,
+            Hand::from_span(&Span::new("22233", 1), false)?,
+            Hand::from_span(&Span::new("AAAKQ", 1), false)?,
+            Hand::from_span(&Span::new("22234", 1), false)?,
+            Hand::from_span(&Span::new("AAKKQ", 1), false)?,
+            Hand::from_span(&Span::new("22334", 1), false)?,
+            Hand::from_span(&Span::new("AAKQJ", 1), false)?,
+            Hand::from_span(&Span::new("22345", 1), false)?,
+            Hand::from_span(&Span::new("AKQJT", 1), false)?,
+            Hand::from_span(&Span::new("23456", 1), false)?,
         ];
         input.sort_unstable();
         assert_eq!(input, expected);
@@ -440,8 +467,14 @@ JJJJ2 41
 
     #[test]
     fn test_hand_ord_3() -> Result<()> {
-        let mut input = [Hand::parse("33322", false)?, Hand::parse("KKKKQ", false)?];
-        let expected = [Hand::parse("KKKKQ", false)?, Hand::parse("33322", false)?];
+        let mut input = [
+            Hand::from_span(&Span::new("33322", 1), false)?,
+            Hand::from_span(&Span::new("KKKKQ", 1), false)?,
+        ];
+        let expected = [
+            Hand::from_span(&Span::new("KKKKQ", 1), false)?,
+            Hand::from_span(&Span::new("33322", 1), false)?,
+        ];
         input.sort_unstable();
         assert_eq!(input, expected);
         Ok(())
@@ -507,4 +540,27 @@ JJJJ2 41
         input.sort_unstable();
         assert_eq!(input, expected);
     }
+
+    fn games() -> impl Strategy<Value = String> {
+        prop::collection::vec(("[AKQJT98765432]{5}", 1..1000usize), 1..20).prop_map(|sets| {
+            sets.iter()
+                .map(|(hand, bid)| format!("{hand} {bid}\n"))
+                .collect()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_game_parse_valid(input in games()) {
+            prop_assert!(Game::parse(&input, false).is_ok());
+            prop_assert!(Game::parse(&input, true).is_ok());
+        }
+
+        #[test]
+        fn test_game_parse_mutated(input in mutated(games()), with_joker in any::<bool>()) {
+            if let Err(err) = Game::parse(&input, with_joker) {
+                prop_assert!(is_positioned(err.as_ref()), "{err:#}");
+            }
+        }
+    }
 }
@@ -1,7 +1,9 @@
 use anyhow::Result;
+use registry::aoc;
 
 use crate::poker;
 
+#[aoc(year = 2023, day = 7, part = 2)]
 pub fn winnings(s: &str) -> Result<usize> {
     poker::play_with_joker(s)
 }
@@ -1,4 +1,3 @@
+pub mod maps;
 pub mod one;
 pub mod two;
-
-mod maps;
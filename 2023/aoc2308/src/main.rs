@@ -1,9 +1,8 @@
-use anyhow::Result;
 use aoc2308::{one, two};
 
 const INPUT: &str = include_str!("../input");
 
-fn main() -> Result<()> {
+fn main() -> Result<(), parse::Report> {
     let steps = one::map_steps(INPUT)?;
     let steps_two = two::map_steps(INPUT)?;
     println!("Map steps 1: {steps}");
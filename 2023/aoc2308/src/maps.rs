@@ -1,4 +1,5 @@
-use anyhow::{anyhow, bail, Context, Error, Result};
+use anyhow::{Context, Result};
+use parse::Span;
 use std::collections::HashMap;
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -22,34 +23,30 @@ impl<'a> Iterator for Map<'a> {
 }
 
 impl<'a> Map<'a> {
-    pub fn parse(s: &str) -> Result<Map> {
-        let mut lines = s.trim().lines();
+    pub fn parse(s: &str) -> Result<Map<'_>> {
+        let mut lines = parse::lines(s).filter(|line| !line.is_empty());
 
-        let directions = lines.next().with_context(|| "getting first line")?;
+        let directions = lines.next().with_context(|| "line 1: missing directions")?;
         let directions = directions
             .chars()
-            .map(|c| match c {
+            .into_iter()
+            .map(|(c, at)| match c {
                 'L' => Ok(Direction::Left),
                 'R' => Ok(Direction::Right),
-                _ => Err(anyhow!("invalid direction: {c}")),
+                _ => Err(at.error(format!("invalid direction: {c}"))),
             })
-            .collect::<Result<Vec<Direction>, Error>>()
+            .collect::<Result<Vec<Direction>, parse::Error>>()
             .with_context(|| "parsing directions")?;
 
-        lines.next(); // skip the empty line
-
         let mut nodes = HashMap::new();
-        for line in lines.rev() {
-            if line.len() != 16 {
-                bail!("invalid line length");
-            }
-
-            let name = &line[0..3];
-            let left = &line[7..10];
-            let right = &line[12..15];
+        for line in lines {
+            let record = line.record()?;
+            let name = node_name(&record.key)?;
+            let left = node_name(&record.left)?;
+            let right = node_name(&record.right)?;
 
             if nodes.insert(name, (left, right)).is_some() {
-                bail!("duplicate node");
+                return Err(record.key.error(format!("duplicate node {name}")).into());
             }
         }
         Ok(Map { directions, nodes })
@@ -82,9 +79,18 @@ impl<'a> Map<'a> {
     }
 }
 
+fn node_name<'a>(span: &Span<'a>) -> Result<&'a str, parse::Error> {
+    match span.as_str().chars().count() {
+        3 => Ok(span.as_str()),
+        _ => Err(span.error(format!("invalid node name {:?}", span.as_str()))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use num::Integer;
+    use parse::testing::{is_positioned, mutated};
+    use proptest::prelude::*;
     use test_case::case;
 
     use super::*;
@@ -133,6 +139,23 @@ XXX = (XXX, XXX)
         Ok(())
     }
 
+    #[case("RL\n\nAAA = (BBB, CCC)\nBBB = (AAA, ZZ)", "line 4, column 13: invalid node name \"ZZ\"" ; "short name")]
+    #[case("RL\n\nAAA = (BBB, CCC)\nAAA = (AAA, ZZZ)", "line 4, column 1: duplicate node AAA" ; "duplicate node")]
+    #[case("RL\n\nAAA = BBB, CCC", "line 3, column 7: expected \"(\"" ; "missing parenthesis")]
+    fn test_map_parse_error(input: &str, expected: &str) {
+        let err = Map::parse(input).expect_err("map should not parse");
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[test]
+    fn test_map_invalid_direction() {
+        let err = Map::parse("\nRLX\n\nAAA = (AAA, AAA)").expect_err("map should not parse");
+        assert_eq!(
+            format!("{err:#}"),
+            "parsing directions: line 2, column 3: invalid direction: X"
+        );
+    }
+
     #[case(INPUT_THREE, 6)]
     fn test_map_many(input: &str, expected: usize) -> anyhow::Result<()> {
         let map = Map::parse(input).with_context(|| "parsing map")?;
@@ -149,4 +172,37 @@ XXX = (XXX, XXX)
         assert_eq!(actual, expected);
         Ok(())
     }
+
+    fn node(idx: usize) -> String {
+        [idx / 676, idx / 26 % 26, idx % 26]
+            .iter()
+            .map(|&c| char::from(b'A' + c as u8))
+            .collect()
+    }
+
+    fn maps() -> impl Strategy<Value = String> {
+        let links = prop::collection::vec((any::<usize>(), any::<usize>()), 1..20);
+        ("[LR]{1,10}", links).prop_map(|(directions, links)| {
+            let mut map = format!("{directions}\n\n");
+            for (idx, (left, right)) in links.iter().enumerate() {
+                let (left, right) = (node(left % links.len()), node(right % links.len()));
+                map.push_str(&format!("{} = ({left}, {right})\n", node(idx)));
+            }
+            map
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_map_parse_valid(input in maps()) {
+            prop_assert!(Map::parse(&input).is_ok());
+        }
+
+        #[test]
+        fn test_map_parse_mutated(input in mutated(maps())) {
+            if let Err(err) = Map::parse(&input) {
+                prop_assert!(is_positioned(err.as_ref()), "{err:#}");
+            }
+        }
+    }
 }
@@ -1,7 +1,9 @@
 use anyhow::{Context, Result};
+use registry::aoc;
 
 use crate::maps::Map;
 
+#[aoc(year = 2023, day = 8, part = 1)]
 pub fn map_steps(s: &str) -> Result<usize> {
     Map::parse(s)
         .with_context(|| "parsing map")?
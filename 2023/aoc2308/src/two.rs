@@ -1,8 +1,10 @@
 use anyhow::{Context, Result};
 use num::Integer;
+use registry::aoc;
 
 use crate::maps::Map;
 
+#[aoc(year = 2023, day = 8, part = 2)]
 pub fn map_steps(s: &str) -> Result<usize> {
     let map = Map::parse(s).with_context(|| "parsing map")?;
     let starts = map.nodes().into_iter().filter(|n| n.ends_with('A'));
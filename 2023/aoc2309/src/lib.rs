@@ -1,4 +1,5 @@
 pub mod one;
+pub mod report;
 pub mod two;
 
 mod mirage;
@@ -1,57 +1,82 @@
-use std::num::ParseIntError;
-
-use anyhow::{bail, Context, Error, Result};
-
-fn parse_isize_vec(s: &str) -> Result<Vec<isize>, Error> {
-    s.split_ascii_whitespace()
-        .map(str::parse::<isize>)
-        .collect::<Result<Vec<isize>, ParseIntError>>()
-        .with_context(|| "parsing isize vec from string")
+use anyhow::{bail, Context, Result};
+use num::{BigInt, Zero};
+use parse::Span;
+
+fn to_isize(value: BigInt) -> Result<isize> {
+    isize::try_from(&value)
+        .ok()
+        .with_context(|| format!("value {value} does not fit in isize"))
 }
 
-pub fn extrapolate_next(s: &str) -> Result<isize> {
-    let mut sequence = parse_isize_vec(s).with_context(|| "parsing sequence")?;
+/// A sequence stored as its Newton forward differences from the first value,
+/// so it can be evaluated exactly at any index before, within or after it.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Sequence {
+    differences: Vec<BigInt>,
+    len: usize,
+}
 
-    if sequence.len() < 2 {
-        bail!("sequence must have length 2 or more")
+impl Sequence {
+    pub fn parse(line: &Span) -> Result<Sequence> {
+        Sequence::new(&line.numbers()?)
     }
 
-    let mut values = vec![*sequence.last().unwrap()];
-
-    while let Some(diff) = diff_sequence(&sequence) {
-        values.push(*diff.last().unwrap());
-        sequence = diff;
+    /// Fails unless some level of differences is constant across at least
+    /// two values, as a shorter level cannot show that it is constant.
+    pub fn new(values: &[isize]) -> Result<Sequence> {
+        if values.len() < 2 {
+            bail!("sequence must have length 2 or more")
+        }
+
+        // after pass k, values[k..] holds the k-th differences
+        let mut pyramid: Vec<BigInt> = values.iter().copied().map(BigInt::from).collect();
+        for level in 0..pyramid.len() - 1 {
+            if level > 0 {
+                for i in (level..pyramid.len()).rev() {
+                    pyramid[i] = &pyramid[i] - &pyramid[i - 1];
+                }
+            }
+
+            if pyramid[level + 1..].iter().all(|d| d == &pyramid[level]) {
+                pyramid.truncate(level + 1);
+                return Ok(Sequence {
+                    differences: pyramid,
+                    len: values.len(),
+                });
+            }
+        }
+
+        bail!(
+            "sequence of length {} does not reduce to a constant difference",
+            values.len()
+        )
     }
 
-    Ok(values.iter().sum())
-}
-
-pub fn extrapolate_previous(s: &str) -> Result<isize> {
-    let mut sequence = parse_isize_vec(s).with_context(|| "parsing sequence")?;
-
-    if sequence.len() < 2 {
-        bail!("sequence must have length 2 or more")
+    pub fn next(&self) -> Result<isize> {
+        to_isize(self.value_at(self.len as isize))
     }
 
-    let mut values = vec![*sequence.first().unwrap()];
-
-    while let Some(diff) = diff_sequence(&sequence) {
-        values.push(*diff.first().unwrap());
-        sequence = diff;
+    pub fn previous(&self) -> Result<isize> {
+        to_isize(self.value_at(-1))
     }
 
-    Ok(values.iter().rev().fold(0, |a, b| b - a))
-}
-
-fn diff_sequence(sequence: &[isize]) -> Option<Vec<isize>> {
-    if sequence.len() < 2 || sequence.iter().all(|a| a == &0) {
-        return None;
+    /// Evaluates the sequence at `index`, where zero is the first value and
+    /// negative indexes extrapolate backwards.
+    pub fn value_at(&self, index: isize) -> BigInt {
+        let index = BigInt::from(index);
+
+        let mut value = BigInt::zero();
+        let mut binomial = BigInt::from(1);
+        for (k, difference) in self.differences.iter().enumerate() {
+            if k > 0 {
+                // k consecutive integers always divide by k!, so this is exact
+                binomial = binomial * (&index - (k - 1)) / k;
+            }
+            value += &binomial * difference;
+        }
+
+        value
     }
-    let mut diff = vec![];
-    for i in 1..sequence.len() {
-        diff.push(sequence[i] - sequence[i - 1]);
-    }
-    Some(diff)
 }
 
 #[cfg(test)]
@@ -64,23 +89,55 @@ mod tests {
     #[case("0 3 6 9 12 15", 18 ; "sequence 1")]
     #[case("1 3 6 10 15 21", 28 ; "sequence 2")]
     #[case("10 13 16 21 30 45", 68 ; "sequence 3")]
-    fn test_extrapolate_next(input: &str, expected: isize) -> Result<()> {
-        assert_eq!(extrapolate_next(input)?, expected);
+    fn test_sequence_next(input: &str, expected: isize) -> Result<()> {
+        assert_eq!(Sequence::parse(&Span::new(input, 1))?.next()?, expected);
         Ok(())
     }
 
     #[case("0 3 6 9 12 15", -3 ; "sequence 1")]
     #[case("1 3 6 10 15 21", 0 ; "sequence 2")]
     #[case("10 13 16 21 30 45", 5 ; "sequence 3")]
-    fn test_extrapolate_previous(input: &str, expected: isize) -> Result<()> {
-        assert_eq!(extrapolate_previous(input)?, expected);
+    fn test_sequence_previous(input: &str, expected: isize) -> Result<()> {
+        assert_eq!(Sequence::parse(&Span::new(input, 1))?.previous()?, expected);
+        Ok(())
+    }
+
+    #[case(&[0, 3, 6, 9, 12, 15], vec![0, 3] ; "sequence 1")]
+    #[case(&[1, 3, 6, 10, 15, 21], vec![1, 2, 1] ; "sequence 2")]
+    #[case(&[10, 13, 16, 21, 30, 45], vec![10, 3, 0, 2] ; "sequence 3")]
+    #[case(&[7, 7], vec![7] ; "constant")]
+    fn test_sequence_differences(input: &[isize], expected: Vec<isize>) -> Result<()> {
+        let expected: Vec<BigInt> = expected.into_iter().map(BigInt::from).collect();
+        assert_eq!(Sequence::new(input)?.differences, expected);
+        Ok(())
+    }
+
+    #[case("0 3 6 9 12 15", -5, -15 ; "linear backwards")]
+    #[case("1 3 6 10 15 21", -5, 6 ; "triangular backwards")]
+    #[case("1 3 6 10 15 21", 1_000, 501_501 ; "triangular forwards")]
+    #[case("10 13 16 21 30 45", 2, 16 ; "within sequence")]
+    #[case("10 13 16 21 30 45", -3, -19 ; "cubic backwards")]
+    fn test_sequence_value_at(input: &str, index: isize, expected: isize) -> Result<()> {
+        assert_eq!(
+            Sequence::parse(&Span::new(input, 1))?.value_at(index),
+            BigInt::from(expected)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_sequence_value_at_beyond_isize() -> Result<()> {
+        let sequence = Sequence::new(&[-isize::MAX, 0, isize::MAX])?;
+        let expected = BigInt::from(isize::MAX) * 2;
+        assert_eq!(sequence.value_at(3), expected);
+        assert!(sequence.next().is_err());
         Ok(())
     }
 
-    #[case(&[0, 3, 6, 9, 12, 15], vec![3, 3, 3, 3, 3] ; "sequence 1")]
-    #[case(&[1, 3, 6, 10, 15, 21], vec![2, 3, 4, 5, 6] ; "sequence 2")]
-    #[case(&[10, 13, 16, 21, 30, 45], vec![3, 3, 5, 9, 15] ; "sequence 3")]
-    fn test_diff_sequence(input: &[isize], expected: Vec<isize>) {
-        assert_eq!(diff_sequence(input), Some(expected));
+    #[case("1 2 4 8 16" ; "powers of two")]
+    #[case("1 2 4" ; "unconfirmed difference")]
+    #[case("5" ; "single value")]
+    fn test_sequence_not_constant(input: &str) {
+        assert!(Sequence::parse(&Span::new(input, 1)).is_err());
     }
 }
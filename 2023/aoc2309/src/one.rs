@@ -1,15 +1,11 @@
-use anyhow::{Context, Error, Result};
+use anyhow::Result;
+use registry::aoc;
 
-use crate::mirage::extrapolate_next;
+use crate::report::Report;
 
+#[aoc(year = 2023, day = 9, part = 1)]
 pub fn extrapolate(s: &str) -> Result<isize> {
-    let values = s
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(extrapolate_next)
-        .collect::<Result<Vec<isize>, Error>>()
-        .with_context(|| "extrapolating sequences")?;
-    Ok(values.into_iter().sum())
+    Ok(Report::parse(s)?.sum_next()?)
 }
 
 #[cfg(test)]
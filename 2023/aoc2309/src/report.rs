@@ -0,0 +1,193 @@
+use anyhow::{anyhow, Result};
+use num::{BigInt, Zero};
+use parse::{Failure, Failures};
+
+use crate::mirage::Sequence;
+
+#[derive(Debug, Default)]
+pub struct Report {
+    sequences: Vec<(usize, Sequence)>,
+}
+
+impl Report {
+    /// Parses every non-empty line, failing with all bad lines rather than
+    /// stopping at the first one.
+    pub fn parse(s: &str) -> Result<Report, Failures> {
+        let mut sequences = vec![];
+        let mut failures = vec![];
+
+        for line in parse::lines(s) {
+            if line.is_empty() {
+                continue;
+            }
+            match Sequence::parse(&line) {
+                Ok(sequence) => sequences.push((line.line(), sequence)),
+                Err(err) => failures.push(Failure::new(line.line(), err)),
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(Failures { failures });
+        }
+
+        Ok(Report { sequences })
+    }
+
+    pub fn sum_next(&self) -> Result<isize, Failures> {
+        self.sum(Sequence::next)
+    }
+
+    pub fn sum_previous(&self) -> Result<isize, Failures> {
+        self.sum(Sequence::previous)
+    }
+
+    /// Adds the values up as a `BigInt`, so only the final total has to fit in
+    /// `isize`, whatever order the lines finish in.
+    fn sum(&self, extrapolate: fn(&Sequence) -> Result<isize>) -> Result<isize, Failures> {
+        let mut failures = vec![];
+        let mut total = BigInt::zero();
+        let mut last = 0;
+
+        for (line, value) in self.evaluate(extrapolate) {
+            match value {
+                Ok(value) => total += value,
+                Err(err) => failures.push(Failure::new(line, err)),
+            }
+            last = last.max(line);
+        }
+        if !failures.is_empty() {
+            return Err(Failures { failures });
+        }
+
+        isize::try_from(&total).map_err(|_| Failures {
+            failures: vec![Failure::new(last, anyhow!("sum {total} overflows isize"))],
+        })
+    }
+
+    #[cfg(not(feature = "concurrency"))]
+    fn evaluate(&self, extrapolate: fn(&Sequence) -> Result<isize>) -> Vec<(usize, Result<isize>)> {
+        self.sequences
+            .iter()
+            .map(|(line, sequence)| (*line, extrapolate(sequence)))
+            .collect()
+    }
+
+    #[cfg(feature = "concurrency")]
+    fn evaluate(&self, extrapolate: fn(&Sequence) -> Result<isize>) -> Vec<(usize, Result<isize>)> {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let chunk_size = self.sequences.len().div_ceil(threads).max(1);
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .sequences
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|(line, sequence)| (*line, extrapolate(sequence)))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("evaluating thread panicked"))
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parse::testing::{is_positioned, mutated};
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example");
+
+    #[test]
+    fn test_report_sums() -> Result<()> {
+        let report = Report::parse(EXAMPLE)?;
+        assert_eq!(report.sum_next()?, 114);
+        assert_eq!(report.sum_previous()?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_report_parse_failures() {
+        let input = "1 2 x\n\n0 3 6\n1 2 4 8 16";
+        let err = Report::parse(input).expect_err("report should not parse");
+        assert_eq!(err.failures.len(), 2);
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 5: invalid number \"x\": invalid digit found in string
+line 4: sequence of length 5 does not reduce to a constant difference"
+        );
+    }
+
+    #[test]
+    fn test_report_value_overflow() -> Result<()> {
+        let input = "
+0 0 0
+-9223372036854775807 0 9223372036854775807
+2 2 2
+";
+        let err = Report::parse(input)?
+            .sum_next()
+            .expect_err("value should overflow");
+        assert_eq!(
+            err.to_string(),
+            "line 3: value 18446744073709551614 does not fit in isize"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_report_sum_overflow() -> Result<()> {
+        let input = "9223372036854775806 9223372036854775806\n2 2 2\n";
+        let err = Report::parse(input)?
+            .sum_next()
+            .expect_err("sum should overflow");
+        assert_eq!(
+            err.to_string(),
+            "line 2: sum 9223372036854775808 overflows isize"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_report_sum_overflows_only_in_between() -> Result<()> {
+        let input = "9223372036854775806 9223372036854775806\n5 5\n-10 -10\n";
+        assert_eq!(Report::parse(input)?.sum_next()?, isize::MAX - 6);
+        Ok(())
+    }
+
+    fn reports() -> impl Strategy<Value = String> {
+        let coefficients = prop::collection::vec(-5..5isize, 1..4);
+        let sequence = (coefficients, 4..12isize).prop_map(|(coefficients, len)| {
+            let values: Vec<String> = (0..len)
+                .map(|x| coefficients.iter().rev().fold(0, |acc, c| acc * x + c))
+                .map(|value| value.to_string())
+                .collect();
+            values.join(" ")
+        });
+        prop::collection::vec(sequence, 0..10).prop_map(|lines| lines.join("\n"))
+    }
+
+    proptest! {
+        #[test]
+        fn test_report_parse_valid(input in reports()) {
+            prop_assert!(Report::parse(&input).is_ok());
+        }
+
+        #[test]
+        fn test_report_parse_mutated(input in mutated(reports())) {
+            if let Err(err) = Report::parse(&input) {
+                prop_assert!(is_positioned(&err), "{err}");
+            }
+        }
+    }
+}
@@ -1,15 +1,11 @@
-use anyhow::{Context, Error, Result};
+use anyhow::Result;
+use registry::aoc;
 
-use crate::mirage::extrapolate_previous;
+use crate::report::Report;
 
+#[aoc(year = 2023, day = 9, part = 2)]
 pub fn extrapolate(s: &str) -> Result<isize> {
-    let values = s
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(extrapolate_previous)
-        .collect::<Result<Vec<isize>, Error>>()
-        .with_context(|| "extrapolating sequences")?;
-    Ok(values.into_iter().sum())
+    Ok(Report::parse(s)?.sum_previous()?)
 }
 
 #[cfg(test)]
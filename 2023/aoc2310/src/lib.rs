@@ -1,2 +1,3 @@
+pub mod maze;
 pub mod one;
 pub mod two;
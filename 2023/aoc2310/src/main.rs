@@ -1,14 +1,13 @@
-use anyhow::Result;
-use aoc2310::one;
+use aoc2310::{one, two};
 
 const INPUT: &str = include_str!("../input");
 
-fn main() -> Result<()> {
-    let steps_1 = one::distance_to_end(INPUT);
-    // let steps_2 = two::extrapolate(INPUT)?;
+fn main() -> Result<(), parse::Report> {
+    let steps_1 = one::distance_to_end(INPUT)?;
+    let enclosed_2 = two::enclosed(INPUT)?;
 
     println!("Distance 1: {steps_1}");
-    // println!("Sum 2: {steps_2}");
+    println!("Enclosed 2: {enclosed_2}");
 
     Ok(())
 }
@@ -0,0 +1,208 @@
+use parse::Span;
+
+type Location = (usize, usize);
+
+const PIPES: [char; 6] = ['|', '-', 'L', 'J', '7', 'F'];
+
+/// A grid of tiles with the start replaced by the pipe it stands in for, and
+/// the loop of pipes running through it.
+#[derive(Debug, Default)]
+pub struct Maze {
+    tiles: Vec<Vec<char>>,
+    main_loop: Vec<Location>,
+}
+
+impl Maze {
+    pub fn parse(s: &str) -> Result<Maze, parse::Error> {
+        let lines: Vec<Span> = parse::lines(s).filter(|line| !line.is_empty()).collect();
+        let spans: Vec<Vec<(char, Span)>> = lines.iter().map(Span::chars).collect();
+
+        let mut tiles = vec![];
+        let mut start = None;
+        for (l, row) in spans.iter().enumerate() {
+            for (c, (tile, at)) in row.iter().enumerate() {
+                match tile {
+                    'S' if start.is_some() => return Err(at.error("found a second start")),
+                    'S' => start = Some((l, c)),
+                    '.' => {}
+                    tile if PIPES.contains(tile) => {}
+                    tile => return Err(at.error(format!("invalid tile {tile:?}"))),
+                }
+            }
+            tiles.push(row.iter().map(|(tile, _)| *tile).collect());
+        }
+
+        let span = |(l, c): Location| spans[l][c].1;
+        let Some(start) = start else {
+            let end = lines.last().map_or(Span::new("", 1), Span::end);
+            return Err(end.error("expected a start tile"));
+        };
+
+        let mut maze = Maze {
+            tiles,
+            main_loop: vec![],
+        };
+
+        // the start is whichever pipe joins the neighbours that lead into it
+        let joined: Vec<Location> = neighbours(start)
+            .filter(|&next| maze.ends(next).is_some_and(|ends| ends.contains(&start)))
+            .collect();
+        let pipe = match joined[..] {
+            [one, two] => PIPES.into_iter().find(|&pipe| {
+                ends(pipe, start).is_some_and(|ends| ends.contains(&one) && ends.contains(&two))
+            }),
+            _ => None,
+        };
+        let Some(pipe) = pipe else {
+            return Err(span(start).error("start does not join exactly two pipes"));
+        };
+        maze.tiles[start.0][start.1] = pipe;
+
+        let mut last = joined[1];
+        let mut current = start;
+        loop {
+            maze.main_loop.push(current);
+            let Some([one, two]) = maze.ends(current) else {
+                let tile = maze.tiles[current.0][current.1];
+                return Err(span(current).error(format!("{tile:?} is not a pipe")));
+            };
+            let next = match (one == last, two == last) {
+                (true, _) => two,
+                (_, true) => one,
+                _ => return Err(span(current).error("pipe does not join the loop")),
+            };
+            if next == start {
+                break;
+            }
+            if maze.tile(next).is_none() {
+                return Err(span(current).error("pipe leads off the map"));
+            }
+            (last, current) = (current, next);
+        }
+
+        Ok(maze)
+    }
+
+    pub fn main_loop(&self) -> &[Location] {
+        &self.main_loop
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[char]> {
+        self.tiles.iter().map(Vec::as_slice)
+    }
+
+    fn tile(&self, (l, c): Location) -> Option<char> {
+        self.tiles.get(l)?.get(c).copied()
+    }
+
+    fn ends(&self, location: Location) -> Option<[Location; 2]> {
+        ends(self.tile(location)?, location)
+    }
+}
+
+/// The two locations a pipe joins, if it is a pipe and neither is off the top
+/// or left of the grid.
+fn ends(pipe: char, (l, c): Location) -> Option<[Location; 2]> {
+    let north = || Some((l.checked_sub(1)?, c));
+    let south = || Some((l + 1, c));
+    let west = || Some((l, c.checked_sub(1)?));
+    let east = || Some((l, c + 1));
+    let ends = match pipe {
+        '|' => [north()?, south()?],
+        '-' => [west()?, east()?],
+        'L' => [north()?, east()?],
+        'J' => [north()?, west()?],
+        '7' => [west()?, south()?],
+        'F' => [east()?, south()?],
+        _ => return None,
+    };
+    Some(ends)
+}
+
+fn neighbours((l, c): Location) -> impl Iterator<Item = Location> {
+    let north = l.checked_sub(1).map(|l| (l, c));
+    let west = c.checked_sub(1).map(|c| (l, c));
+    [north, Some((l + 1, c)), west, Some((l, c + 1))]
+        .into_iter()
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use parse::testing::{is_positioned, mutated};
+    use proptest::prelude::*;
+    use test_case::case;
+
+    use super::*;
+
+    #[case(".....\n.S-7.\n.|.|.\n.L-J.\n.....", 8 ; "square")]
+    #[case("\n..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...\n", 16 ; "winding")]
+    fn test_maze_main_loop(input: &str, expected: usize) -> Result<(), parse::Error> {
+        assert_eq!(Maze::parse(input)?.main_loop().len(), expected);
+        Ok(())
+    }
+
+    #[case("S-7\n|.|\nL-X\n", "line 3, column 3: invalid tile 'X'" ; "invalid tile")]
+    #[case("S-7\n|S|\nL-J\n", "line 2, column 2: found a second start" ; "second start")]
+    #[case("F-7\n|.|\nL-J\n", "line 3, column 4: expected a start tile" ; "no start")]
+    #[case("S-7\n..|\nL-J\n", "line 1, column 1: start does not join exactly two pipes" ; "one neighbour")]
+    #[case("S-7\n|.|\nL-.\n", "line 3, column 3: '.' is not a pipe" ; "broken loop")]
+    #[case("S-7\n|.|\nL-F\n", "line 3, column 3: pipe does not join the loop" ; "wrong turn")]
+    #[case("S-7\n|.|\nL--", "line 3, column 3: pipe leads off the map" ; "off the map")]
+    fn test_maze_parse_error(input: &str, expected: &str) {
+        let err = Maze::parse(input).expect_err("maze should not parse");
+        assert_eq!(err.to_string(), expected);
+    }
+
+    /// A rectangular loop of pipes inside a margin of ground, with the start
+    /// somewhere on its edge.
+    fn mazes() -> impl Strategy<Value = String> {
+        (
+            2..8usize,
+            2..8usize,
+            0..3usize,
+            any::<prop::sample::Index>(),
+        )
+            .prop_map(|(width, height, margin, start)| {
+                let edge: Vec<Location> = (0..width)
+                    .map(|c| (0, c))
+                    .chain((1..height).map(|l| (l, width - 1)))
+                    .chain((0..width - 1).rev().map(|c| (height - 1, c)))
+                    .chain((1..height - 1).rev().map(|l| (l, 0)))
+                    .collect();
+                let start = edge[start.index(edge.len())];
+
+                let mut maze = vec![vec!['.'; width + 2 * margin]; height + 2 * margin];
+                for &(l, c) in &edge {
+                    let tile = match (l, c) {
+                        _ if (l, c) == start => 'S',
+                        (0, 0) => 'F',
+                        (0, c) if c == width - 1 => '7',
+                        (l, 0) if l == height - 1 => 'L',
+                        (l, c) if l == height - 1 && c == width - 1 => 'J',
+                        (0, _) => '-',
+                        (l, _) if l == height - 1 => '-',
+                        _ => '|',
+                    };
+                    maze[l + margin][c + margin] = tile;
+                }
+                maze.iter()
+                    .map(|row| format!("{}\n", row.iter().collect::<String>()))
+                    .collect()
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn test_maze_parse_valid(input in mazes()) {
+            prop_assert!(Maze::parse(&input).is_ok());
+        }
+
+        #[test]
+        fn test_maze_parse_mutated(input in mutated(mazes())) {
+            if let Err(err) = Maze::parse(&input) {
+                prop_assert!(is_positioned(&err), "{err:#}");
+            }
+        }
+    }
+}
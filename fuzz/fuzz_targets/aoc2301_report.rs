@@ -0,0 +1,19 @@
+#![no_main]
+
+use aoc2301::{
+    calibration::{Mode, Report},
+    vocabulary::Vocabulary,
+};
+use libfuzzer_sys::fuzz_target;
+use parse::testing::is_positioned;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    for vocabulary in [Vocabulary::digits(), Vocabulary::english()] {
+        if let Err(err) = Report::parse(input, &vocabulary, Mode::Strict) {
+            assert!(is_positioned(&err), "{err}");
+        }
+    }
+});
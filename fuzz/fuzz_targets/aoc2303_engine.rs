@@ -0,0 +1,18 @@
+#![no_main]
+
+use aoc2303::{config::SchematicConfig, engine::Engine};
+use libfuzzer_sys::fuzz_target;
+use parse::testing::is_positioned;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    let strict = SchematicConfig {
+        strict: true,
+        ..Default::default()
+    };
+    if let Err(err) = Engine::parse_with(input, &strict) {
+        assert!(is_positioned(err.as_ref()), "{err:#}");
+    }
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use aoc2305::farm::Almanac;
+use libfuzzer_sys::fuzz_target;
+use parse::testing::is_positioned;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    // seed ranges are expanded in full, so only the seed list is fuzzed
+    if let Err(err) = Almanac::with_seed_list(input) {
+        assert!(is_positioned(err.as_ref()), "{err:#}");
+    }
+});
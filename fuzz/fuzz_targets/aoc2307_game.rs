@@ -0,0 +1,16 @@
+#![no_main]
+
+use aoc2307::poker::Game;
+use libfuzzer_sys::fuzz_target;
+use parse::testing::is_positioned;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    for with_joker in [false, true] {
+        if let Err(err) = Game::parse(input, with_joker) {
+            assert!(is_positioned(err.as_ref()), "{err:#}");
+        }
+    }
+});
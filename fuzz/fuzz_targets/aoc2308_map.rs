@@ -0,0 +1,14 @@
+#![no_main]
+
+use aoc2308::maps::Map;
+use libfuzzer_sys::fuzz_target;
+use parse::testing::is_positioned;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Err(err) = Map::parse(input) {
+        assert!(is_positioned(err.as_ref()), "{err:#}");
+    }
+});
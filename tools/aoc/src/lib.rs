@@ -0,0 +1,3 @@
+pub mod puzzle;
+pub mod scaffold;
+pub mod workspace;
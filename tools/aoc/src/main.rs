@@ -0,0 +1,166 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use aoc::{
+    scaffold::{scaffold, Day},
+    workspace,
+};
+
+// link every day so its solutions are registered
+use aoc2201 as _;
+use aoc2301 as _;
+use aoc2302 as _;
+use aoc2303 as _;
+use aoc2304 as _;
+use aoc2305 as _;
+use aoc2306 as _;
+use aoc2307 as _;
+use aoc2308 as _;
+use aoc2309 as _;
+use aoc2310 as _;
+
+const USAGE: &str = "usage: aoc new <year> <day> --module <name> [--puzzle <puzzle.md>]
+       aoc list
+       aoc run <year> <day> <part> [--input <path>]
+       aoc synth <year> <day> --output <path> [--seed <n>] [--size <n>]";
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("new") => new(&args[1..]),
+        Some("list") => list(),
+        Some("run") => run(&args[1..]),
+        Some("synth") => synth(&args[1..]),
+        _ => bail!("{USAGE}"),
+    }
+}
+
+fn workspace_root() -> Result<PathBuf> {
+    let dir = std::env::current_dir().with_context(|| "reading the current directory")?;
+    workspace::root(&dir)
+}
+
+fn list() -> Result<()> {
+    for solution in registry::solutions() {
+        println!(
+            "{} day {:>2} part {}: {}",
+            solution.year, solution.day, solution.part, solution.name
+        );
+    }
+    Ok(())
+}
+
+fn run(args: &[String]) -> Result<()> {
+    let [year, day, part, rest @ ..] = args else {
+        bail!("{USAGE}")
+    };
+    let year: u16 = year
+        .parse()
+        .with_context(|| format!("parsing year {year:?}"))?;
+    let day: u8 = day
+        .parse()
+        .with_context(|| format!("parsing day {day:?}"))?;
+    let part: u8 = part
+        .parse()
+        .with_context(|| format!("parsing part {part:?}"))?;
+
+    let path = match rest {
+        [] => workspace_root()?.join(format!("{year}/aoc{:02}{day:02}/input", year % 100)),
+        [flag, path] if flag == "--input" => PathBuf::from(path),
+        _ => bail!("{USAGE}"),
+    };
+    let input = fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+
+    println!("{}", registry::run(year, day, part, &input)?);
+
+    Ok(())
+}
+
+fn synth(args: &[String]) -> Result<()> {
+    let [year, day, rest @ ..] = args else {
+        bail!("{USAGE}")
+    };
+    let year: u16 = year
+        .parse()
+        .with_context(|| format!("parsing year {year:?}"))?;
+    let day: u8 = day
+        .parse()
+        .with_context(|| format!("parsing day {day:?}"))?;
+    let synth =
+        synth::find(year, day).with_context(|| format!("no generator for {year} day {day}"))?;
+
+    let mut output = None;
+    let mut seed = 0;
+    let mut size = synth.size;
+    let mut rest = rest.iter();
+    while let Some(flag) = rest.next() {
+        let value = rest
+            .next()
+            .with_context(|| format!("{flag} needs a value"))?;
+        match flag.as_str() {
+            "--output" => output = Some(value),
+            "--seed" => {
+                seed = value
+                    .parse()
+                    .with_context(|| format!("parsing seed {value:?}"))?
+            }
+            "--size" => {
+                size = value
+                    .parse()
+                    .with_context(|| format!("parsing size {value:?}"))?
+            }
+            _ => bail!("unknown argument: {flag}\n{USAGE}"),
+        }
+    }
+    let output = output.with_context(|| format!("--output is required\n{USAGE}"))?;
+
+    let puzzle = synth.generate(seed, size)?;
+    fs::write(output, &puzzle.input).with_context(|| format!("writing {output}"))?;
+
+    println!("Wrote {output}");
+    for (part, answer) in puzzle.answers.iter().enumerate() {
+        println!("part {}: {answer}", part + 1);
+    }
+
+    Ok(())
+}
+
+fn new(args: &[String]) -> Result<()> {
+    let [year, day, rest @ ..] = args else {
+        bail!("{USAGE}")
+    };
+    let year = year
+        .parse()
+        .with_context(|| format!("parsing year {year:?}"))?;
+    let day = day
+        .parse()
+        .with_context(|| format!("parsing day {day:?}"))?;
+
+    let mut module = None;
+    let mut puzzle = None;
+    let mut rest = rest.iter();
+    while let Some(flag) = rest.next() {
+        let value = rest
+            .next()
+            .with_context(|| format!("{flag} needs a value"))?;
+        match flag.as_str() {
+            "--module" => module = Some(value),
+            "--puzzle" => puzzle = Some(value),
+            _ => bail!("unknown argument: {flag}\n{USAGE}"),
+        }
+    }
+
+    let module = module.with_context(|| format!("--module is required\n{USAGE}"))?;
+    let puzzle = puzzle
+        .map(|path| fs::read_to_string(path).with_context(|| format!("reading {path}")))
+        .transpose()?;
+
+    let day = Day::new(year, day, module)?;
+    let dir = scaffold(&workspace_root()?, &day, puzzle.as_deref())?;
+
+    println!("Created {}", dir.display());
+
+    Ok(())
+}
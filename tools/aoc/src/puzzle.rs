@@ -0,0 +1,84 @@
+/// The example inputs quoted in a saved `puzzle.md`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Examples {
+    pub one: Option<String>,
+    pub two: Option<String>,
+}
+
+/// Takes the first fenced block of each part as its example, falling back to
+/// part one's example when part two does not quote its own.
+pub fn examples(markdown: &str) -> Examples {
+    let (one, two) = match markdown.find("--- Part Two ---") {
+        Some(idx) => markdown.split_at(idx),
+        None => (markdown, ""),
+    };
+
+    let one = first_block(one);
+    let two = first_block(two).or_else(|| one.clone());
+
+    Examples { one, two }
+}
+
+fn first_block(markdown: &str) -> Option<String> {
+    let mut lines = markdown.lines().skip_while(|line| !is_fence(line));
+    lines.next()?;
+
+    let mut block: Vec<&str> = lines.take_while(|line| !is_fence(line)).collect();
+    while block.last().is_some_and(|line| line.trim().is_empty()) {
+        block.pop();
+    }
+
+    match block.is_empty() {
+        true => None,
+        false => Some(block.join("\n") + "\n"),
+    }
+}
+
+fn is_fence(line: &str) -> bool {
+    line.trim_start().starts_with("```")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let markdown = "
+\\--- Day 6: Wait For It ---
+
+For example:
+
+```
+Time:      7  15   30
+Distance:  9  40  200
+
+```
+
+\\--- Part Two ---
+----------
+
+```
+Time:      71530
+```
+";
+        let expected = Examples {
+            one: Some("Time:      7  15   30\nDistance:  9  40  200\n".to_string()),
+            two: Some("Time:      71530\n".to_string()),
+        };
+        assert_eq!(examples(markdown), expected);
+    }
+
+    #[test]
+    fn test_examples_part_two_reuses_part_one() {
+        let markdown = "```\n1\n2\n```\n\\--- Part Two ---\nNo example here.\n";
+        let examples = examples(markdown);
+        assert_eq!(examples.one.as_deref(), Some("1\n2\n"));
+        assert_eq!(examples.two, examples.one);
+    }
+
+    #[test]
+    fn test_examples_none() {
+        assert_eq!(examples("no examples at all"), Examples::default());
+    }
+}
@@ -0,0 +1,254 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{puzzle, workspace};
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.tmpl");
+const BUILD_RS: &str = include_str!("../templates/build.rs.tmpl");
+const LIB_RS: &str = include_str!("../templates/lib.rs.tmpl");
+const MAIN_RS: &str = include_str!("../templates/main.rs.tmpl");
+const MODULE_RS: &str = include_str!("../templates/module.rs.tmpl");
+const PART_RS: &str = include_str!("../templates/part.rs.tmpl");
+
+#[derive(Debug)]
+pub struct Day {
+    year: u16,
+    day: u8,
+    module: String,
+}
+
+impl Day {
+    pub fn new(year: u16, day: u8, module: &str) -> Result<Day> {
+        if !(2015..=2099).contains(&year) {
+            bail!("year {year} is not an Advent of Code year")
+        }
+        if !(1..=25).contains(&day) {
+            bail!("day {day} is not between 1 and 25")
+        }
+
+        let valid = module
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_lowercase())
+            && module
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !valid || ["one", "two", "lib", "main"].contains(&module) {
+            bail!("module {module:?} is not a usable module name")
+        }
+
+        Ok(Day {
+            year,
+            day,
+            module: module.to_string(),
+        })
+    }
+
+    /// The crate name, like `aoc2311` for 2023 day 11.
+    pub fn crate_name(&self) -> String {
+        format!("aoc{:02}{:02}", self.year % 100, self.day)
+    }
+
+    /// The crate directory relative to the workspace root.
+    pub fn member(&self) -> String {
+        format!("{}/{}", self.year, self.crate_name())
+    }
+}
+
+/// Writes a new day crate under `root`, registers it in the workspace and
+/// links it into the `aoc` tool, refusing to touch a day that already exists
+/// and removing the crate again if any step fails. When a saved `puzzle.md`
+/// is given, its examples are filled into the generated tests.
+pub fn scaffold(root: &Path, day: &Day, puzzle: Option<&str>) -> Result<PathBuf> {
+    let dir = root.join(day.member());
+    if dir.exists() {
+        bail!("{} already exists", dir.display())
+    }
+
+    let examples = puzzle.map(puzzle::examples).unwrap_or_default();
+    let files = [
+        ("Cargo.toml", render(CARGO_TOML, day, "", None)),
+        ("build.rs", render(BUILD_RS, day, "", None)),
+        ("src/lib.rs", render(LIB_RS, day, "", None)),
+        ("src/main.rs", render(MAIN_RS, day, "", None)),
+        (
+            &format!("src/{}.rs", day.module),
+            render(MODULE_RS, day, "", None),
+        ),
+        (
+            "src/one.rs",
+            render(PART_RS, day, "one", examples.one.as_deref()),
+        ),
+        (
+            "src/two.rs",
+            render(PART_RS, day, "two", examples.two.as_deref()),
+        ),
+    ];
+
+    // leave nothing half made behind if any step fails
+    let made = write(&dir, files).and_then(|()| {
+        workspace::register(root, &day.member())?;
+        workspace::link(root, &day.crate_name(), &day.member())
+    });
+    if let Err(err) = made {
+        let _ = fs::remove_dir_all(&dir);
+        return Err(err);
+    }
+
+    Ok(dir)
+}
+
+fn write<'a>(dir: &Path, files: impl IntoIterator<Item = (&'a str, String)>) -> Result<()> {
+    fs::create_dir_all(dir.join("src")).with_context(|| format!("creating {}", dir.display()))?;
+    for (name, contents) in files {
+        let path = dir.join(name);
+        fs::write(&path, contents).with_context(|| format!("writing {}", path.display()))?;
+    }
+    Ok(())
+}
+
+fn render(template: &str, day: &Day, part: &str, example: Option<&str>) -> String {
+    let part_number = match part {
+        "one" => "1",
+        "two" => "2",
+        _ => "",
+    };
+
+    template
+        .replace("{{crate}}", &day.crate_name())
+        .replace("{{year}}", &day.year.to_string())
+        .replace("{{day}}", &day.day.to_string())
+        .replace("{{module}}", &day.module)
+        .replace("{{part}}", part)
+        .replace("{{part_number}}", part_number)
+        .replace("{{example}}", &escape(example.unwrap_or_default()))
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(2023, 11, "cosmos", "aoc2311" ; "two digit day")]
+    #[test_case(2024, 1, "calories", "aoc2401" ; "one digit day")]
+    fn test_day_crate_name(year: u16, day: u8, module: &str, expected: &str) -> Result<()> {
+        assert_eq!(Day::new(year, day, module)?.crate_name(), expected);
+        Ok(())
+    }
+
+    #[test_case(2023, 26, "cosmos" ; "day out of range")]
+    #[test_case(1999, 1, "cosmos" ; "year out of range")]
+    #[test_case(2023, 11, "Cosmos" ; "uppercase module")]
+    #[test_case(2023, 11, "one" ; "clashing module")]
+    #[test_case(2023, 11, "" ; "empty module")]
+    fn test_day_invalid(year: u16, day: u8, module: &str) {
+        assert!(Day::new(year, day, module).is_err());
+    }
+
+    #[test]
+    fn test_render_escapes_example() -> Result<()> {
+        let day = Day::new(2023, 11, "cosmos")?;
+        let rendered = render("\"{{example}}\"", &day, "one", Some("a \"b\" \\c\n"));
+        assert_eq!(rendered, "\"a \\\"b\\\" \\\\c\n\"");
+        Ok(())
+    }
+
+    #[test]
+    fn test_scaffold() -> Result<()> {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(&root)?;
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"2023/*\"]\n",
+        )?;
+        fs::create_dir_all(root.join("tools/aoc/src"))?;
+        fs::write(
+            root.join("tools/aoc/Cargo.toml"),
+            "[dependencies]\naoc2310 = { path = \"../../2023/aoc2310\" }\n",
+        )?;
+        fs::write(
+            root.join("tools/aoc/src/main.rs"),
+            "use aoc2310 as _;\n\nfn main() {}\n",
+        )?;
+
+        let day = Day::new(2024, 11, "cosmos")?;
+        let dir = scaffold(&root, &day, Some("```\n#.#\n```\n"))?;
+
+        let one = fs::read_to_string(dir.join("src/one.rs"))?;
+        assert!(one.contains("const EXAMPLE: &str = \"#.#\n\";"));
+        assert!(dir.join("src/cosmos.rs").exists());
+        assert!(fs::read_to_string(root.join("Cargo.toml"))?.contains("\"2024/*\""));
+        assert!(fs::read_to_string(root.join("tools/aoc/Cargo.toml"))?.contains("aoc2411 = "));
+        assert!(
+            fs::read_to_string(root.join("tools/aoc/src/main.rs"))?.contains("use aoc2411 as _;")
+        );
+        assert!(
+            scaffold(&root, &day, None).is_err(),
+            "existing day must not be overwritten"
+        );
+
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+
+    /// Builds a scaffolded day in a workspace of its own, linked to the real
+    /// tools crates, so the templates can't drift from what compiles.
+    #[cfg(unix)]
+    #[test]
+    fn test_scaffold_builds() -> Result<()> {
+        let tools = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .canonicalize()?;
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-build-{}", std::process::id()));
+        fs::create_dir_all(root.join("tools/aoc/src"))?;
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nresolver = \"2\"\nmembers = []\n",
+        )?;
+        for tool in [
+            "aoc_input",
+            "parse",
+            "parse_derive",
+            "registry",
+            "registry_macros",
+        ] {
+            std::os::unix::fs::symlink(tools.join(tool), root.join("tools").join(tool))?;
+        }
+        fs::write(root.join("tools/aoc/Cargo.toml"), "[dependencies]\n")?;
+        fs::write(root.join("tools/aoc/src/main.rs"), "use aoc2310 as _;\n")?;
+
+        let day = Day::new(2024, 11, "cosmos")?;
+        let dir = scaffold(&root, &day, Some("```\n#.#\n```\n"))?;
+        fs::write(dir.join("input"), "#.#\n")?;
+
+        // a target directory beside the one running this test, which cargo
+        // keeps locked until the tests finish
+        let target = std::env::current_exe()?
+            .ancestors()
+            .nth(3)
+            .context("finding the target directory")?
+            .join("scaffold");
+        let output = std::process::Command::new(env!("CARGO"))
+            .args(["build", "--offline", "--all-targets"])
+            .current_dir(&root)
+            .env("AOC_OFFLINE", "1")
+            .env("CARGO_TARGET_DIR", target)
+            .output()?;
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+}
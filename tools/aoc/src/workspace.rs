@@ -0,0 +1,217 @@
+use std::{
+    fs,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+
+/// The nearest directory at or above `from` whose `Cargo.toml` declares a
+/// `[workspace]`.
+pub fn root(from: &Path) -> Result<PathBuf> {
+    for dir in from.ancestors() {
+        let path = dir.join("Cargo.toml");
+        let Ok(manifest) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if manifest.lines().any(|line| line.trim() == "[workspace]") {
+            return Ok(dir.to_path_buf());
+        }
+    }
+    bail!("no workspace Cargo.toml at or above {}", from.display())
+}
+
+/// Adds a `<year>/*` member to the workspace manifest unless `member` is
+/// already covered, returning whether the manifest changed.
+pub fn register(root: &Path, member: &str) -> Result<bool> {
+    let path = root.join("Cargo.toml");
+    let manifest =
+        fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+
+    let Some(manifest) = with_member(&manifest, member)? else {
+        return Ok(false);
+    };
+
+    fs::write(&path, manifest).with_context(|| format!("writing {}", path.display()))?;
+    Ok(true)
+}
+
+/// Links the day crate `name` at `member` into the `aoc` tool, as a
+/// dependency and a `use` in its `main.rs`, so its solutions get registered.
+pub fn link(root: &Path, name: &str, member: &str) -> Result<()> {
+    let tool = root.join("tools/aoc");
+    let manifest = tool.join("Cargo.toml");
+    let main = tool.join("src/main.rs");
+    let read = |path: &Path| {
+        fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))
+    };
+
+    // work out both edits before writing either
+    let manifest_contents = with_dependency(&read(&manifest)?, name, member)
+        .with_context(|| format!("adding {name} to {}", manifest.display()))?;
+    let main_contents = with_use(&read(&main)?, name)
+        .with_context(|| format!("adding {name} to {}", main.display()))?;
+
+    for (path, contents) in [(manifest, manifest_contents), (main, main_contents)] {
+        fs::write(&path, contents).with_context(|| format!("writing {}", path.display()))?;
+    }
+    Ok(())
+}
+
+fn with_member(manifest: &str, member: &str) -> Result<Option<String>> {
+    let (year, _) = member
+        .split_once('/')
+        .with_context(|| format!("member {member:?} is not in a year directory"))?;
+
+    let mut lines: Vec<String> = manifest.lines().map(str::to_string).collect();
+    let Some(line) = lines
+        .iter_mut()
+        .find(|line| line.trim_start().starts_with("members"))
+    else {
+        bail!("workspace manifest has no members list")
+    };
+
+    let Some(list) = line
+        .split_once('=')
+        .and_then(|(_, list)| list.trim().strip_prefix('['))
+        .and_then(|list| list.strip_suffix(']'))
+    else {
+        bail!("workspace members must be a single-line list: {line}")
+    };
+
+    let mut members: Vec<String> = list
+        .split(',')
+        .map(|member| member.trim().trim_matches('"').to_string())
+        .filter(|member| !member.is_empty())
+        .collect();
+
+    let glob = format!("{year}/*");
+    if members.iter().any(|m| m == member || *m == glob) {
+        return Ok(None);
+    }
+
+    members.push(glob);
+    members.sort();
+
+    let quoted: Vec<String> = members.iter().map(|m| format!("\"{m}\"")).collect();
+    *line = format!("members = [{}]", quoted.join(", "));
+
+    Ok(Some(lines.join("\n") + "\n"))
+}
+
+fn with_dependency(manifest: &str, name: &str, member: &str) -> Result<String> {
+    let lines: Vec<String> = manifest.lines().map(str::to_string).collect();
+    let Some(header) = lines.iter().position(|l| l == "[dependencies]") else {
+        bail!("manifest has no [dependencies] table")
+    };
+    let block = block(&lines, header + 1, |l| !l.is_empty() && !l.starts_with('['));
+    if lines[block.clone()]
+        .iter()
+        .any(|l| l.split_once(" =").is_some_and(|(dep, _)| dep == name))
+    {
+        bail!("{name} is already a dependency")
+    }
+
+    let line = format!("{name} = {{ version = \"0.1.0\", path = \"../../{member}\" }}");
+    Ok(with_sorted(lines, block, line))
+}
+
+fn with_use(main: &str, name: &str) -> Result<String> {
+    let is_link = |l: &str| l.starts_with("use aoc") && l.ends_with(" as _;");
+    let lines: Vec<String> = main.lines().map(str::to_string).collect();
+    let Some(first) = lines.iter().position(|l| is_link(l)) else {
+        bail!("main.rs has no `use aocXXXX as _;` lines")
+    };
+    let block = block(&lines, first, |l| is_link(l));
+    let line = format!("use {name} as _;");
+    if lines[block.clone()].contains(&line) {
+        bail!("{name} is already used")
+    }
+
+    Ok(with_sorted(lines, block, line))
+}
+
+/// The run of lines from `start` that all match `within`.
+fn block(lines: &[String], start: usize, within: impl Fn(&str) -> bool) -> Range<usize> {
+    let len = lines[start..].iter().take_while(|l| within(l)).count();
+    start..start + len
+}
+
+/// Adds `line` to the end of `block` and sorts it back into place.
+fn with_sorted(mut lines: Vec<String>, block: Range<usize>, line: String) -> String {
+    lines.insert(block.end, line);
+    lines[block.start..=block.end].sort();
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "[workspace]
+resolver = \"2\"
+members = [\"2022/*\", \"2023/*\", \"tools/*\"]
+";
+
+    #[test]
+    fn test_root() -> Result<()> {
+        let root = std::env::temp_dir().join(format!("aoc-root-{}", std::process::id()));
+        let member = root.join("2023/aoc2311/src");
+        fs::create_dir_all(&member)?;
+        fs::write(root.join("Cargo.toml"), MANIFEST)?;
+        fs::write(root.join("2023/aoc2311/Cargo.toml"), "[package]\n")?;
+
+        assert_eq!(super::root(&member)?, root);
+        assert_eq!(super::root(&root)?, root);
+
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_member_covered() -> Result<()> {
+        assert_eq!(with_member(MANIFEST, "2023/aoc2311")?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_member_new_year() -> Result<()> {
+        let expected = "[workspace]
+resolver = \"2\"
+members = [\"2022/*\", \"2023/*\", \"2024/*\", \"tools/*\"]
+";
+        assert_eq!(
+            with_member(MANIFEST, "2024/aoc2401")?.as_deref(),
+            Some(expected)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_dependency() -> Result<()> {
+        let manifest = "[package]\nname = \"aoc\"\n\n[dependencies]\nanyhow = \"1\"\naoc2310 = { path = \"x\" }\nregistry = { path = \"y\" }\n";
+        let expected = "[package]\nname = \"aoc\"\n\n[dependencies]\nanyhow = \"1\"\naoc2310 = { path = \"x\" }\naoc2311 = { version = \"0.1.0\", path = \"../../2023/aoc2311\" }\nregistry = { path = \"y\" }\n";
+        assert_eq!(
+            with_dependency(manifest, "aoc2311", "2023/aoc2311")?,
+            expected
+        );
+        assert!(with_dependency(manifest, "aoc2310", "2023/aoc2310").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_use() -> Result<()> {
+        let main = "use anyhow::Result;\n\nuse aoc2309 as _;\nuse aoc2310 as _;\n\nfn main() {}\n";
+        let expected = "use anyhow::Result;\n\nuse aoc2309 as _;\nuse aoc2310 as _;\nuse aoc2311 as _;\n\nfn main() {}\n";
+        assert_eq!(with_use(main, "aoc2311")?, expected);
+        assert!(with_use(main, "aoc2310").is_err());
+        assert!(with_use("fn main() {}\n", "aoc2311").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_member_multiline() {
+        let manifest = "[workspace]\nmembers = [\n  \"2023/*\",\n]\n";
+        assert!(with_member(manifest, "2024/aoc2401").is_err());
+    }
+}
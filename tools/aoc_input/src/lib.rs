@@ -1,13 +1,35 @@
-use anyhow::Result;
+use std::{env, path::Path};
+
+use anyhow::{Context, Result};
 use aoc_client::{AocClient, PuzzleDay, PuzzleYear};
 
+/// Skips downloading altogether when set, for builds that only need the
+/// inputs already in the repository, like CI.
+const OFFLINE: &str = "AOC_OFFLINE";
+
+/// Where `aoc_client` looks for a session before trying its cookie files.
+const SESSION: &str = "ADVENT_OF_CODE_SESSION";
+
+/// Saves the day's input and puzzle text next to the calling build script.
+///
+/// Without a session cookie it keeps the saved input if there is one, and
+/// only fails if there isn't.
 pub fn download(year: PuzzleYear, day: PuzzleDay) -> Result<()> {
-    let client = AocClient::builder()
-        .overwrite_files(true)
-        .session_cookie_from_default_locations()?
-        .year(year)?
-        .day(day)?
-        .build()?;
+    println!("cargo:rerun-if-env-changed={OFFLINE}");
+    println!("cargo:rerun-if-env-changed={SESSION}");
+    if env::var_os(OFFLINE).is_some() {
+        return Ok(());
+    }
+
+    let mut builder = AocClient::builder();
+    builder.overwrite_files(true);
+    if let Err(err) = builder.session_cookie_from_default_locations() {
+        if Path::new("input").exists() {
+            return Ok(());
+        }
+        return Err(err).context("no Advent of Code session and no saved input");
+    }
+    let client = builder.year(year)?.day(day)?.build()?;
 
     client.save_input()?;
     client.save_puzzle_markdown()?;
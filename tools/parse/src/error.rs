@@ -0,0 +1,125 @@
+use std::{
+    fmt::{self, Display},
+    ops::Range,
+};
+
+/// A parse failure at a position in the input. It keeps the line it came
+/// from and the byte range it covers in the whole input, and renders the line
+/// with the range underlined when formatted with `{:#}`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    line: usize,
+    column: usize,
+    span: Range<usize>,
+    width: usize,
+    message: String,
+    source: String,
+}
+
+impl Error {
+    pub(crate) fn new(
+        line: usize,
+        column: usize,
+        span: Range<usize>,
+        width: usize,
+        message: String,
+        source: &str,
+    ) -> Error {
+        let source = source.to_string();
+        Error {
+            line,
+            column,
+            span,
+            width,
+            message,
+            source,
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The byte range of the offending text in the whole input.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The source line behind a numbered gutter, with carets under the
+    /// offending text.
+    pub fn snippet(&self) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let padding: String = self
+            .source
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let carets = "^".repeat(self.width.max(1));
+        format!(
+            "{gutter} |\n{number} | {}\n{gutter} | {padding}{carets}",
+            self.source
+        )
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        if f.alternate() {
+            write!(f, "\n{}", self.snippet())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Span};
+
+    #[test]
+    fn test_error_snippet() -> Result<(), Error> {
+        let (_, numbers) = Span::new("Card 1: 4 x | 5", 3).label()?;
+        let err = numbers
+            .numbers::<usize>()
+            .expect_err("numbers should not parse");
+        assert_eq!(
+            format!("{err:#}"),
+            "line 3, column 11: invalid number \"x\": invalid digit found in string
+  |
+3 | Card 1: 4 x | 5
+  |           ^"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_span_underlines_text() {
+        let input = "Game 1: 3 blue\nGame 2: 4 teal\n";
+        let line = crate::lines(input).nth(1).expect("second line");
+        let err = line.split(" ")[3].error("unknown colour \"teal\"");
+        assert_eq!(err.span(), 25..29);
+        assert_eq!(&input[err.span()], "teal");
+        assert_eq!(
+            err.snippet(),
+            "  |
+2 | Game 2: 4 teal
+  |           ^^^^"
+        );
+    }
+}
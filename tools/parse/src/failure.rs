@@ -0,0 +1,103 @@
+use std::fmt::{self, Display};
+
+use crate::Error;
+
+/// A line that failed, for reports that check every line before giving up
+/// rather than stopping at the first bad one.
+#[derive(Debug)]
+pub struct Failure {
+    pub line: usize,
+    pub column: Option<usize>,
+    pub reason: String,
+}
+
+impl Failure {
+    /// A failure on `line`. When `err` is a parse [`Error`], its own line and
+    /// column win over `line`, since a span already knows where it sits in
+    /// the whole input.
+    pub fn new(line: usize, err: anyhow::Error) -> Failure {
+        let err = match err.downcast::<Error>() {
+            Ok(err) => return Failure::from(err),
+            Err(err) => err,
+        };
+        let reason = format!("{err:#}");
+        Failure {
+            line,
+            column: None,
+            reason,
+        }
+    }
+}
+
+impl From<Error> for Failure {
+    fn from(err: Error) -> Failure {
+        Failure {
+            line: err.line(),
+            column: Some(err.column()),
+            reason: err.message().to_string(),
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.column {
+            Some(column) => write!(f, "line {}, column {column}: {}", self.line, self.reason),
+            None => write!(f, "line {}: {}", self.line, self.reason),
+        }
+    }
+}
+
+/// Every failed line of a report, one per line when displayed.
+#[derive(Debug)]
+pub struct Failures {
+    pub failures: Vec<Failure>,
+}
+
+impl Display for Failures {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, failure) in self.failures.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{failure}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Failures {}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+    use crate::Span;
+
+    #[test]
+    fn test_failure_prefers_error_line() {
+        let input = "1 2\n3 x\n";
+        let err = crate::lines(input)
+            .map(|line| line.numbers::<u32>())
+            .collect::<Result<Vec<_>, _>>()
+            .expect_err("line 2 should not parse");
+        let failure = Failure::new(7, err.into());
+        assert_eq!((failure.line, failure.column), (2, Some(3)));
+    }
+
+    #[test]
+    fn test_failures_display() {
+        let failures = Failures {
+            failures: vec![
+                Failure::new(2, Span::new("1 x", 2).numbers::<u32>().unwrap_err().into()),
+                Failure::new(4, anyhow!("no digit found")),
+            ],
+        };
+        assert_eq!(
+            failures.to_string(),
+            "line 2, column 3: invalid number \"x\": invalid digit found in string
+line 4: no digit found"
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{Error, Span};
+
+/// Builds a value from a positioned piece of input, which is what
+/// `#[derive(AocParse)]` calls for each field of its format.
+pub trait FromSpan: Sized {
+    fn from_span(span: &Span) -> Result<Self, Error>;
+}
+
+macro_rules! from_span_number {
+    ($($t:ty),*) => {
+        $(
+            impl FromSpan for $t {
+                fn from_span(span: &Span) -> Result<$t, Error> {
+                    span.trim().number()
+                }
+            }
+        )*
+    };
+}
+
+from_span_number!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl FromSpan for String {
+    fn from_span(span: &Span) -> Result<String, Error> {
+        Ok(span.trim().as_str().to_string())
+    }
+}
+
+impl FromSpan for char {
+    fn from_span(span: &Span) -> Result<char, Error> {
+        let span = span.trim();
+        let mut chars = span.as_str().chars();
+        match (chars.next(), chars.next()) {
+            (Some(char), None) => Ok(char),
+            _ => Err(span.error(format!("expected one character, found {:?}", span.as_str()))),
+        }
+    }
+}
+
+/// Whitespace-separated values.
+impl<T: FromSpan> FromSpan for Vec<T> {
+    fn from_span(span: &Span) -> Result<Vec<T>, Error> {
+        span.tokens().iter().map(T::from_span).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::AocParse;
+
+    #[derive(Debug, PartialEq, Eq, AocParse)]
+    #[parse(format = "Card {id}: {winning} | {scratch}")]
+    struct Card {
+        id: usize,
+        winning: Vec<usize>,
+        scratch: Vec<usize>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, AocParse)]
+    #[parse(format = "{key} = ({left}, {right})")]
+    struct Node {
+        key: String,
+        left: String,
+        right: String,
+    }
+
+    #[derive(Debug, PartialEq, Eq, AocParse)]
+    #[parse(format = "{{{name}}}: {values}")]
+    struct Braced {
+        name: char,
+        #[parse(separator = ",")]
+        values: Vec<isize>,
+    }
+
+    #[test]
+    fn test_derive_card() -> Result<(), Error> {
+        let card: Card = "Card   3:  1 21 | 69  1".parse()?;
+        let expected = Card {
+            id: 3,
+            winning: vec![1, 21],
+            scratch: vec![69, 1],
+        };
+        assert_eq!(card, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_derive_trailing_literal() -> Result<(), Error> {
+        let node: Node = "AAA = (BBB, CCC)".parse()?;
+        assert_eq!(node.key, "AAA");
+        assert_eq!(node.left, "BBB");
+        assert_eq!(node.right, "CCC");
+        Ok(())
+    }
+
+    #[test]
+    fn test_derive_separator_and_braces() -> Result<(), Error> {
+        let braced: Braced = "{x}: 1, -2,3".parse()?;
+        let expected = Braced {
+            name: 'x',
+            values: vec![1, -2, 3],
+        };
+        assert_eq!(braced, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_derive_from_span_keeps_line() {
+        let err =
+            Card::from_span(&Span::new("Card 1: 2 | x", 7)).expect_err("card should not parse");
+        assert_eq!(
+            err.to_string(),
+            "line 7, column 13: invalid number \"x\": invalid digit found in string"
+        );
+    }
+
+    #[test_case("Crd 1: 2 | 3", "line 1, column 1: expected \"Card \"" ; "bad prefix")]
+    #[test_case("Card 1 2 | 3", "line 1, column 13: expected \": \"" ; "missing colon")]
+    #[test_case("Card 1: 2 3", "line 1, column 12: expected \" | \"" ; "missing bar")]
+    fn test_derive_card_error(input: &str, expected: &str) {
+        let err = input.parse::<Card>().expect_err("card should not parse");
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[test_case("AAA = (BBB, CCC" ; "missing close")]
+    #[test_case("AAA = BBB, CCC)" ; "missing open")]
+    fn test_derive_node_error(input: &str) {
+        assert!(input.parse::<Node>().is_err());
+    }
+
+    #[test]
+    fn test_from_span_vec() -> Result<(), Error> {
+        let values: Vec<usize> = FromSpan::from_span(&Span::new(" 41 48  83 ", 1))?;
+        assert_eq!(values, vec![41, 48, 83]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_span_char() {
+        let err = char::from_span(&Span::new(" ab", 1)).expect_err("char should not parse");
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 2: expected one character, found \"ab\""
+        );
+    }
+}
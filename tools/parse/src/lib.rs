@@ -0,0 +1,128 @@
+//! Shared helpers for reading puzzle input, where every piece of text keeps
+//! the line and column it came from so errors can point back at it.
+
+extern crate self as parse;
+
+mod error;
+mod failure;
+mod from_span;
+mod report;
+mod span;
+pub mod testing;
+
+pub use error::Error;
+pub use failure::{Failure, Failures};
+pub use from_span::FromSpan;
+pub use parse_derive::AocParse;
+pub use report::Report;
+pub use span::{Record, Span};
+
+/// Every line of the input, numbered from 1.
+pub fn lines(s: &str) -> impl Iterator<Item = Span<'_>> {
+    s.lines()
+        .enumerate()
+        .map(move |(idx, line)| Span::within(s, line, idx + 1))
+}
+
+/// Whitespace-separated numbers from a single line of text.
+pub fn numbers<T>(s: &str) -> Result<Vec<T>, Error>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    Span::new(s, 1).numbers()
+}
+
+/// Groups of non-blank lines, split wherever one or more blank lines occur.
+pub fn sections(s: &str) -> Vec<Vec<Span<'_>>> {
+    let mut sections = vec![];
+    let mut current = vec![];
+
+    for line in lines(s) {
+        if line.as_str().trim().is_empty() {
+            if !current.is_empty() {
+                sections.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        current.push(line);
+    }
+    if !current.is_empty() {
+        sections.push(current);
+    }
+
+    sections
+}
+
+/// A section that opens with a `header: values` line, like `seeds: 79 14` or
+/// `seed-to-soil map:` followed by its body lines.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Block<'a> {
+    pub header: Span<'a>,
+    pub values: Span<'a>,
+    pub body: Vec<Span<'a>>,
+}
+
+/// Splits the input into blank-line-separated blocks. Sections that don't
+/// open with a header line are skipped, like any other text between blocks.
+pub fn blocks(s: &str) -> Vec<Block<'_>> {
+    sections(s)
+        .into_iter()
+        .filter_map(|section| {
+            let (header, values) = section[0].label().ok()?;
+            let body = section[1..].to_vec();
+            Some(Block {
+                header,
+                values,
+                body,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections() {
+        let sections = sections("\na\nb\n\n\nc\n");
+        let text: Vec<Vec<&str>> = sections
+            .iter()
+            .map(|s| s.iter().map(Span::as_str).collect())
+            .collect();
+        assert_eq!(text, vec![vec!["a", "b"], vec!["c"]]);
+        assert_eq!(sections[1][0].line(), 6);
+    }
+
+    #[test]
+    fn test_blocks() -> Result<(), Error> {
+        let blocks = blocks("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50 48\n");
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].header.as_str(), "seeds");
+        assert_eq!(blocks[0].values.numbers::<usize>()?, vec![79, 14]);
+        assert!(blocks[0].body.is_empty());
+        assert_eq!(blocks[1].header.as_str(), "seed-to-soil map");
+        assert!(blocks[1].values.is_empty());
+        assert_eq!(blocks[1].body[1].numbers::<usize>()?, vec![52, 50, 48]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_blocks_missing_header() {
+        let blocks = blocks("seeds: 1\n\n50 98 2\n\nsoil map:\n1 2 3\n");
+        let headers: Vec<&str> = blocks.iter().map(|b| b.header.as_str()).collect();
+        assert_eq!(headers, vec!["seeds", "soil map"]);
+    }
+
+    #[test]
+    fn test_numbers() -> Result<(), Error> {
+        assert_eq!(numbers::<isize>(" 0 -3  6 ")?, vec![0, -3, 6]);
+        let err = numbers::<isize>("0 3 x").expect_err("numbers should not parse");
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 5: invalid number \"x\": invalid digit found in string"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use std::fmt::{self, Debug, Display};
+
+use crate::Error;
+
+/// An error for a day's `main` to return. When it exits the binary it prints
+/// the whole context chain and, if a parse [`Error`] caused it, the offending
+/// input line with the failing text underlined.
+pub struct Report(anyhow::Error);
+
+impl Report {
+    /// The parse error somewhere in the chain, if there is one.
+    pub fn diagnostic(&self) -> Option<&Error> {
+        self.0.chain().find_map(|err| err.downcast_ref::<Error>())
+    }
+}
+
+impl From<anyhow::Error> for Report {
+    fn from(err: anyhow::Error) -> Report {
+        Report(err)
+    }
+}
+
+impl From<Error> for Report {
+    fn from(err: Error) -> Report {
+        Report(err.into())
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#}", self.0)?;
+        if let Some(diagnostic) = self.diagnostic() {
+            write!(f, "\n{}", diagnostic.snippet())?;
+        }
+        Ok(())
+    }
+}
+
+// `main` prints the error it returns with `Debug`
+impl Debug for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Context;
+
+    use crate::{lines, Report};
+
+    #[test]
+    fn test_report_renders_diagnostic() {
+        let input = "seeds: 1 2\nsoil: 3 x\n";
+        let err = lines(input)
+            .map(|line| line.label()?.1.numbers::<usize>())
+            .collect::<Result<Vec<_>, _>>()
+            .with_context(|| "parsing almanac")
+            .expect_err("almanac should not parse");
+        let report = Report::from(err);
+        assert_eq!(report.diagnostic().map(|err| err.span()), Some(19..20));
+        assert_eq!(
+            format!("{report:?}"),
+            "parsing almanac: line 2, column 9: invalid number \"x\": invalid digit found in string
+  |
+2 | soil: 3 x
+  |         ^"
+        );
+    }
+
+    #[test]
+    fn test_report_without_diagnostic() {
+        let report = Report::from(anyhow::anyhow!("no input"));
+        assert!(report.diagnostic().is_none());
+        assert_eq!(format!("{report:?}"), "no input");
+    }
+}
@@ -0,0 +1,295 @@
+use std::{fmt::Display, ops::Range, str::FromStr};
+
+use crate::Error;
+
+/// A piece of one input line, remembering where it sits in that line and
+/// where that line starts in the whole input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span<'a> {
+    text: &'a str,
+    source: &'a str,
+    line: usize,
+    column: usize,
+    offset: usize,
+}
+
+impl<'a> Span<'a> {
+    /// Covers all of `source`, which is line `line` of the input.
+    pub fn new(source: &'a str, line: usize) -> Span<'a> {
+        Span {
+            text: source,
+            source,
+            line,
+            column: 1,
+            offset: 0,
+        }
+    }
+
+    /// Covers line `line` of `input`, which must be a slice of `input`.
+    pub(crate) fn within(input: &'a str, source: &'a str, line: usize) -> Span<'a> {
+        let offset = source.as_ptr() as usize - input.as_ptr() as usize;
+        Span {
+            offset,
+            ..Span::new(source, line)
+        }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The 1-based column, in chars, where the span starts.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// The byte range the span covers in the whole input.
+    pub fn range(&self) -> Range<usize> {
+        let start = self.offset + self.text.as_ptr() as usize - self.source.as_ptr() as usize;
+        start..start + self.text.len()
+    }
+
+    /// An error underlining the span.
+    pub fn error(&self, message: impl Into<String>) -> Error {
+        let width = self.text.chars().count();
+        Error::new(
+            self.line,
+            self.column,
+            self.range(),
+            width,
+            message.into(),
+            self.source,
+        )
+    }
+
+    fn error_at_end(&self, message: impl Into<String>) -> Error {
+        self.end().error(message)
+    }
+
+    /// An empty span just past the end of this one.
+    pub fn end(&self) -> Span<'a> {
+        self.slice(self.text.len(), self.text.len())
+    }
+
+    /// Narrows the span to the byte range `start..end` of its text.
+    fn slice(&self, start: usize, end: usize) -> Span<'a> {
+        Span {
+            text: &self.text[start..end],
+            source: self.source,
+            line: self.line,
+            column: self.column + self.text[..start].chars().count(),
+            offset: self.offset,
+        }
+    }
+
+    pub fn trim(&self) -> Span<'a> {
+        let start = self.text.len() - self.text.trim_start().len();
+        let end = self.text.trim_end().len();
+        match start <= end {
+            true => self.slice(start, end),
+            false => self.slice(start, start),
+        }
+    }
+
+    pub fn strip_prefix(&self, prefix: &str) -> Result<Span<'a>, Error> {
+        match self.text.starts_with(prefix) {
+            true => Ok(self.slice(prefix.len(), self.text.len())),
+            false => Err(self.error(format!("expected {prefix:?}"))),
+        }
+    }
+
+    pub fn strip_suffix(&self, suffix: &str) -> Result<Span<'a>, Error> {
+        match self.text.ends_with(suffix) {
+            true => Ok(self.slice(0, self.text.len() - suffix.len())),
+            false => Err(self.error_at_end(format!("expected {suffix:?}"))),
+        }
+    }
+
+    /// Splits around the first `delimiter`, pointing at the end of the span
+    /// when it is missing.
+    pub fn split_once(&self, delimiter: &str) -> Result<(Span<'a>, Span<'a>), Error> {
+        let Some(idx) = self.text.find(delimiter) else {
+            return Err(self.error_at_end(format!("expected {delimiter:?}")));
+        };
+        let before = self.slice(0, idx);
+        let after = self.slice(idx + delimiter.len(), self.text.len());
+        Ok((before, after))
+    }
+
+    pub fn split(&self, delimiter: &str) -> Vec<Span<'a>> {
+        let mut parts = vec![];
+        let mut start = 0;
+        for (idx, _) in self.text.match_indices(delimiter) {
+            parts.push(self.slice(start, idx));
+            start = idx + delimiter.len();
+        }
+        parts.push(self.slice(start, self.text.len()));
+        parts
+    }
+
+    /// Every char of the span alongside a span covering just that char.
+    pub fn chars(&self) -> Vec<(char, Span<'a>)> {
+        self.text
+            .char_indices()
+            .map(|(idx, char)| (char, self.slice(idx, idx + char.len_utf8())))
+            .collect()
+    }
+
+    /// The whitespace-separated words of the span.
+    pub fn tokens(&self) -> Vec<Span<'a>> {
+        let mut tokens = vec![];
+        let mut start = None;
+        for (idx, char) in self.text.char_indices() {
+            match (char.is_whitespace(), start) {
+                (true, Some(from)) => {
+                    tokens.push(self.slice(from, idx));
+                    start = None;
+                }
+                (false, None) => start = Some(idx),
+                _ => {}
+            }
+        }
+        if let Some(from) = start {
+            tokens.push(self.slice(from, self.text.len()));
+        }
+        tokens
+    }
+
+    pub fn number<T>(&self) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text
+            .parse()
+            .map_err(|err| self.error(format!("invalid number {:?}: {err}", self.text)))
+    }
+
+    pub fn numbers<T>(&self) -> Result<Vec<T>, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.tokens().iter().map(Span::number).collect()
+    }
+
+    /// Splits a `Label: values` line into its trimmed label and values.
+    pub fn label(&self) -> Result<(Span<'a>, Span<'a>), Error> {
+        let (label, values) = self.split_once(":")?;
+        Ok((label.trim(), values.trim()))
+    }
+
+    /// The values of a `Label: values` line whose label must be `name`.
+    pub fn expect_label(&self, name: &str) -> Result<Span<'a>, Error> {
+        let (label, values) = self.label()?;
+        match label.as_str() == name {
+            true => Ok(values),
+            false => Err(label.error(format!("expected label {name:?}"))),
+        }
+    }
+
+    /// Reads a `key = (left, right)` line.
+    pub fn record(&self) -> Result<Record<'a>, Error> {
+        let (key, value) = self.split_once("=")?;
+        let value = value.trim().strip_prefix("(")?.strip_suffix(")")?;
+        let (left, right) = value.split_once(",")?;
+        Ok(Record {
+            key: key.trim(),
+            left: left.trim(),
+            right: right.trim(),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Record<'a> {
+    pub key: Span<'a>,
+    pub left: Span<'a>,
+    pub right: Span<'a>,
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test]
+    fn test_span_tokens() {
+        let tokens = Span::new("  12 ab\tc ", 1).tokens();
+        let found: Vec<(&str, usize)> = tokens.iter().map(|t| (t.as_str(), t.column())).collect();
+        assert_eq!(found, vec![("12", 3), ("ab", 6), ("c", 9)]);
+    }
+
+    #[test]
+    fn test_span_columns_count_chars() -> Result<(), Error> {
+        let (_, values) = Span::new("é: 5", 1).label()?;
+        assert_eq!(values.column(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_span_chars() {
+        let chars = Span::new("é2K", 1).chars();
+        let found: Vec<(char, usize, Range<usize>)> = chars
+            .iter()
+            .map(|(c, span)| (*c, span.column(), span.range()))
+            .collect();
+        assert_eq!(found, vec![('é', 1, 0..2), ('2', 2, 2..3), ('K', 3, 3..4)]);
+    }
+
+    #[test]
+    fn test_span_label() -> Result<(), Error> {
+        let values = Span::new("Time:      7  15   30", 1).expect_label("Time")?;
+        assert_eq!(values.numbers::<usize>()?, vec![7, 15, 30]);
+        assert_eq!(values.column(), 12);
+        Ok(())
+    }
+
+    #[test_case("Tme: 7", "line 1, column 1: expected label \"Time\"" ; "wrong label")]
+    #[test_case("Time 7", "line 1, column 7: expected \":\"" ; "missing colon")]
+    #[test_case("Time: 7 1x", "line 1, column 9: invalid number \"1x\": invalid digit found in string" ; "bad number")]
+    fn test_span_label_error(input: &str, expected: &str) {
+        let err = Span::new(input, 1)
+            .expect_label("Time")
+            .and_then(|values| values.numbers::<usize>())
+            .expect_err("label should not parse");
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[test]
+    fn test_span_split() {
+        let parts = Span::new("3 blue, 4 red; 1 red", 1).split("; ");
+        let found: Vec<(&str, usize)> = parts.iter().map(|p| (p.as_str(), p.column())).collect();
+        assert_eq!(found, vec![("3 blue, 4 red", 1), ("1 red", 16)]);
+    }
+
+    #[test]
+    fn test_span_record() -> Result<(), Error> {
+        let record = Span::new("AAA = (BBB, CCC)", 1).record()?;
+        assert_eq!(record.key.as_str(), "AAA");
+        assert_eq!(record.left.as_str(), "BBB");
+        assert_eq!(record.right.as_str(), "CCC");
+        assert_eq!(record.right.column(), 13);
+        Ok(())
+    }
+
+    #[test_case("AAA (BBB, CCC)", "line 1, column 15: expected \"=\"" ; "missing equals")]
+    #[test_case("AAA = BBB, CCC)", "line 1, column 7: expected \"(\"" ; "missing open")]
+    #[test_case("AAA = (BBB, CCC", "line 1, column 16: expected \")\"" ; "missing close")]
+    #[test_case("AAA = (BBB CCC)", "line 1, column 15: expected \",\"" ; "missing comma")]
+    fn test_span_record_error(input: &str, expected: &str) {
+        let err = Span::new(input, 1)
+            .record()
+            .expect_err("record should not parse");
+        assert_eq!(err.to_string(), expected);
+    }
+}
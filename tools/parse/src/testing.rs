@@ -0,0 +1,143 @@
+//! Helpers for checking that day parsers fail cleanly on malformed input,
+//! shared by the property tests and the fuzz targets.
+
+use crate::Error;
+
+/// Whether `err`, or anything in its source chain, says where the input went
+/// wrong: either a parse [`Error`] or a message starting with `line N`.
+pub fn is_positioned(err: &(dyn std::error::Error + 'static)) -> bool {
+    let mut next = Some(err);
+    while let Some(err) = next {
+        if err.is::<Error>() || names_line(&err.to_string()) {
+            return true;
+        }
+        next = err.source();
+    }
+    false
+}
+
+fn names_line(message: &str) -> bool {
+    message
+        .strip_prefix("line ")
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+#[cfg(feature = "proptest")]
+pub use strategy::mutated;
+
+#[cfg(feature = "proptest")]
+mod strategy {
+    use proptest::{prelude::*, sample::Index};
+
+    /// Text spliced into inputs: separators and symbols the days parse, plus
+    /// values that overflow or are not ASCII.
+    const FRAGMENTS: &[&str] = &[
+        "0",
+        "7",
+        "-",
+        " ",
+        "  ",
+        "\n",
+        "\n\n",
+        "\t",
+        ":",
+        ";",
+        ",",
+        "|",
+        "=",
+        "(",
+        ")",
+        ".",
+        "S",
+        "L",
+        "J",
+        "F",
+        "é",
+        "99999999999999999999",
+        "-9223372036854775808",
+    ];
+
+    #[derive(Clone, Debug)]
+    enum Edit {
+        Insert(&'static str),
+        Replace(&'static str),
+        Delete,
+        Truncate,
+        DuplicateLine,
+    }
+
+    fn edit() -> impl Strategy<Value = (Edit, Index)> {
+        let fragment = proptest::sample::select(FRAGMENTS);
+        let edit = prop_oneof![
+            3 => fragment.clone().prop_map(Edit::Insert),
+            3 => fragment.prop_map(Edit::Replace),
+            2 => Just(Edit::Delete),
+            1 => Just(Edit::Truncate),
+            1 => Just(Edit::DuplicateLine),
+        ];
+        (edit, any::<Index>())
+    }
+
+    /// Inputs from `valid` with a few random edits applied, so most of each
+    /// input still looks right and parsing gets some way before failing.
+    pub fn mutated(valid: impl Strategy<Value = String>) -> impl Strategy<Value = String> {
+        (valid, proptest::collection::vec(edit(), 1..4)).prop_map(|(mut input, edits)| {
+            for (edit, at) in edits {
+                apply(&mut input, edit, at);
+            }
+            input
+        })
+    }
+
+    fn apply(input: &mut String, edit: Edit, at: Index) {
+        let boundaries: Vec<usize> = input
+            .char_indices()
+            .map(|(idx, _)| idx)
+            .chain([input.len()])
+            .collect();
+        let start = boundaries[at.index(boundaries.len())];
+        let end = boundaries
+            .iter()
+            .find(|&&idx| idx > start)
+            .copied()
+            .unwrap_or(start);
+
+        match edit {
+            Edit::Insert(fragment) => input.insert_str(start, fragment),
+            Edit::Replace(fragment) => input.replace_range(start..end, fragment),
+            Edit::Delete => input.replace_range(start..end, ""),
+            Edit::Truncate => input.truncate(start),
+            Edit::DuplicateLine => {
+                let line_start = input[..start].rfind('\n').map_or(0, |idx| idx + 1);
+                let line_end = input[start..]
+                    .find('\n')
+                    .map_or(input.len(), |idx| start + idx);
+                let line = format!("{}\n", &input[line_start..line_end]);
+                input.insert_str(line_start, &line);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::Span;
+
+    #[test_case("line 3: no digit found", true ; "line message")]
+    #[test_case("line three", false ; "no number")]
+    #[test_case("totalling winnings", false ; "no line")]
+    fn test_is_positioned_message(message: &str, expected: bool) {
+        let err = anyhow::anyhow!(message.to_string());
+        assert_eq!(is_positioned(err.as_ref()), expected);
+    }
+
+    #[test]
+    fn test_is_positioned_chain() {
+        let err = Span::new("x", 1).error("bad");
+        let err = anyhow::Error::from(err).context("parsing sets");
+        assert!(is_positioned(err.as_ref()));
+    }
+}
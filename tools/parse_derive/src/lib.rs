@@ -0,0 +1,257 @@
+//! `#[derive(AocParse)]`, which turns a format string into a parser. See the
+//! `parse` crate for the runtime it generates calls to.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr, Result};
+
+/// Implements `parse::FromSpan` and `FromStr` for a struct with named fields
+/// from a format like `#[parse(format = "Card {id}: {winning} | {scratch}")]`.
+///
+/// Each `{field}` takes the text up to the literal that follows it, or the
+/// rest of the input for the last field, and parses it with `FromSpan`. A
+/// field marked `#[parse(separator = ";")]` is split on the separator and each
+/// part parsed into its `Vec`. Write `{{` or `}}` for a literal brace.
+#[proc_macro_derive(AocParse, attributes(parse))]
+pub fn derive_aoc_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+fn expand(input: DeriveInput) -> Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(name, "AocParse only supports structs"));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(name, "AocParse needs named fields"));
+    };
+
+    let format = struct_format(&input)?;
+    let segments = segments(&format)?;
+
+    let mut placeholders = vec![];
+    for segment in &segments {
+        if let Segment::Field(field) = segment {
+            if !fields
+                .named
+                .iter()
+                .any(|f| f.ident.as_ref().is_some_and(|i| i == field))
+            {
+                return Err(Error::new(
+                    format.span(),
+                    format!("format names unknown field `{field}`"),
+                ));
+            }
+            if placeholders.contains(field) {
+                return Err(Error::new(
+                    format.span(),
+                    format!("field `{field}` appears twice in the format"),
+                ));
+            }
+            placeholders.push(field.clone());
+        }
+    }
+
+    let mut steps = vec![];
+    let mut idx = 0;
+    while idx < segments.len() {
+        match (&segments[idx], segments.get(idx + 1)) {
+            (Segment::Literal(literal), _) => steps.push(quote! {
+                let rest = rest.strip_prefix(#literal)?;
+            }),
+            (Segment::Field(field), None) => {
+                let ident = format_ident!("__{}", field);
+                steps.push(quote! { let #ident = rest; });
+            }
+            (Segment::Field(field), Some(Segment::Literal(literal))) => {
+                let ident = format_ident!("__{}", field);
+                match idx + 2 == segments.len() {
+                    true => steps.push(quote! { let #ident = rest.strip_suffix(#literal)?; }),
+                    false => {
+                        steps.push(quote! { let (#ident, rest) = rest.split_once(#literal)?; })
+                    }
+                }
+                idx += 1;
+            }
+            (Segment::Field(field), Some(Segment::Field(next))) => {
+                return Err(Error::new(
+                    format.span(),
+                    format!("fields `{field}` and `{next}` need literal text between them"),
+                ))
+            }
+        }
+        idx += 1;
+    }
+
+    let mut values = vec![];
+    for field in &fields.named {
+        let ident = field.ident.as_ref().expect("named field");
+        if !placeholders.iter().any(|p| ident == p) {
+            return Err(Error::new_spanned(
+                ident,
+                format!("field `{ident}` is missing from the format"),
+            ));
+        }
+
+        let span = format_ident!("__{}", ident);
+        let value = match field_separator(field)? {
+            Some(separator) => quote! {
+                #span
+                    .split(#separator)
+                    .iter()
+                    .map(::parse::FromSpan::from_span)
+                    .collect::<::std::result::Result<_, ::parse::Error>>()?
+            },
+            None => quote! { ::parse::FromSpan::from_span(&#span)? },
+        };
+        values.push(quote! { #ident: #value });
+    }
+
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::parse::FromSpan for #name #type_generics #where_clause {
+            fn from_span(span: &::parse::Span) -> ::std::result::Result<Self, ::parse::Error> {
+                let rest = *span;
+                #(#steps)*
+                ::std::result::Result::Ok(#name { #(#values),* })
+            }
+        }
+
+        impl #impl_generics ::std::str::FromStr for #name #type_generics #where_clause {
+            type Err = ::parse::Error;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, ::parse::Error> {
+                <Self as ::parse::FromSpan>::from_span(&::parse::Span::new(s, 1))
+            }
+        }
+    })
+}
+
+fn struct_format(input: &DeriveInput) -> Result<LitStr> {
+    let mut format = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("parse")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("format") {
+                format = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `format = \"...\"`"))
+            }
+        })?;
+    }
+    format.ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "AocParse needs #[parse(format = \"...\")]",
+        )
+    })
+}
+
+fn field_separator(field: &syn::Field) -> Result<Option<LitStr>> {
+    let mut separator = None;
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("parse")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("separator") {
+                separator = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `separator = \"...\"`"))
+            }
+        })?;
+    }
+    Ok(separator)
+}
+
+fn segments(format: &LitStr) -> Result<Vec<Segment>> {
+    let mut segments = vec![];
+    let mut literal = String::new();
+    let mut chars = format
+        .value()
+        .chars()
+        .collect::<Vec<_>>()
+        .into_iter()
+        .peekable();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '{' if chars.next_if_eq(&'{').is_some() => literal.push('{'),
+            '}' if chars.next_if_eq(&'}').is_some() => literal.push('}'),
+            '{' => {
+                let mut field = String::new();
+                let mut closed = false;
+                for char in chars.by_ref() {
+                    if char == '}' {
+                        closed = true;
+                        break;
+                    }
+                    field.push(char);
+                }
+                if !closed {
+                    return Err(Error::new(format.span(), "unterminated `{` in format"));
+                }
+                if field.is_empty() || !field.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    return Err(Error::new(
+                        format.span(),
+                        format!("invalid placeholder `{{{field}}}`"),
+                    ));
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Field(field));
+            }
+            '}' => return Err(Error::new(format.span(), "unmatched `}` in format")),
+            _ => literal.push(char),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("Game {id" , "unterminated `{` in format" ; "unterminated")]
+    #[test_case("Game id}" , "unmatched `}` in format" ; "unmatched")]
+    #[test_case("Game {}" , "invalid placeholder `{}`" ; "empty placeholder")]
+    #[test_case("Game {a b}" , "invalid placeholder `{a b}`" ; "invalid placeholder")]
+    fn test_segments_error(format: &str, expected: &str) {
+        let format = LitStr::new(format, Span::call_site());
+        let err = segments(&format).err().expect("format should not parse");
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[test]
+    fn test_segments() -> Result<()> {
+        let format = LitStr::new("{{{name}}}: {values}", Span::call_site());
+        let segments: Vec<String> = segments(&format)?
+            .into_iter()
+            .map(|segment| match segment {
+                Segment::Literal(literal) => format!("literal {literal}"),
+                Segment::Field(field) => format!("field {field}"),
+            })
+            .collect();
+        assert_eq!(
+            segments,
+            ["literal {", "field name", "literal }: ", "field values"]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+//! A link-time list of every solution marked with `#[aoc(year, day, part)]`,
+//! which can be looked up and run by date.
+
+extern crate self as registry;
+
+use std::fmt::Display;
+
+use anyhow::{bail, Context, Result};
+
+#[doc(hidden)]
+pub use inventory;
+pub use registry_macros::aoc;
+
+/// One part of one day's puzzle.
+#[derive(Debug)]
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// The path of the registered function, like `aoc2305::two::closest`.
+    pub name: &'static str,
+    solve: fn(&str) -> Result<String>,
+}
+
+inventory::collect!(Solution);
+
+impl Solution {
+    #[doc(hidden)]
+    pub const fn new(
+        year: u16,
+        day: u8,
+        part: u8,
+        name: &'static str,
+        solve: fn(&str) -> Result<String>,
+    ) -> Solution {
+        Solution {
+            year,
+            day,
+            part,
+            name,
+            solve,
+        }
+    }
+
+    pub fn solve(&self, input: &str) -> Result<String> {
+        (self.solve)(input)
+    }
+}
+
+/// Every registered solution linked into the binary, ordered by date and
+/// part.
+pub fn solutions() -> Vec<&'static Solution> {
+    let mut solutions: Vec<&Solution> = inventory::iter::<Solution>().collect();
+    solutions.sort_by_key(|s| (s.year, s.day, s.part, s.name));
+    solutions
+}
+
+/// The solution registered for the part, refusing to pick between two.
+pub fn find(year: u16, day: u8, part: u8) -> Result<Option<&'static Solution>> {
+    let mut found =
+        inventory::iter::<Solution>().filter(|s| (s.year, s.day, s.part) == (year, day, part));
+    let first = found.next();
+    if let (Some(first), Some(second)) = (first, found.next()) {
+        bail!(
+            "{year} day {day} part {part} is registered twice, by {} and {}",
+            first.name,
+            second.name
+        )
+    }
+    Ok(first)
+}
+
+pub fn run(year: u16, day: u8, part: u8, input: &str) -> Result<String> {
+    let solution = find(year, day, part)?
+        .with_context(|| format!("no solution registered for {year} day {day} part {part}"))?;
+    solution
+        .solve(input)
+        .with_context(|| format!("running {}", solution.name))
+}
+
+/// A value a solution can return, turned into the answer to print.
+pub trait Answer {
+    fn into_answer(self) -> Result<String>;
+}
+
+macro_rules! answer_display {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                fn into_answer(self) -> Result<String> {
+                    Ok(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+answer_display!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, String);
+
+impl<T, E> Answer for Result<T, E>
+where
+    T: Display,
+    E: Into<anyhow::Error>,
+{
+    fn into_answer(self) -> Result<String> {
+        self.map(|answer| answer.to_string()).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[aoc(year = 1999, day = 1, part = 1)]
+    fn count_lines(s: &str) -> usize {
+        s.lines().count()
+    }
+
+    #[aoc(year = 1999, day = 1, part = 2)]
+    fn first_number(s: &str) -> Result<u32> {
+        Ok(s.lines().next().context("empty input")?.parse()?)
+    }
+
+    #[aoc(year = 1999, day = 3, part = 1)]
+    fn first_line(s: &str) -> String {
+        s.lines().next().unwrap_or_default().to_string()
+    }
+
+    #[aoc(year = 1999, day = 3, part = 1)]
+    fn last_line(s: &str) -> String {
+        s.lines().last().unwrap_or_default().to_string()
+    }
+
+    #[test]
+    fn test_solutions() {
+        let found: Vec<(u16, u8, u8, &str)> = solutions()
+            .iter()
+            .map(|s| (s.year, s.day, s.part, s.name))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (1999, 1, 1, "registry::tests::count_lines"),
+                (1999, 1, 2, "registry::tests::first_number"),
+                (1999, 3, 1, "registry::tests::first_line"),
+                (1999, 3, 1, "registry::tests::last_line"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run() -> Result<()> {
+        assert_eq!(run(1999, 1, 1, "7\n8\n")?, "2");
+        assert_eq!(run(1999, 1, 2, "7\n8\n")?, "7");
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_errors() {
+        let err = run(1999, 2, 1, "").expect_err("day should not be registered");
+        assert_eq!(
+            err.to_string(),
+            "no solution registered for 1999 day 2 part 1"
+        );
+
+        let err = run(1999, 1, 2, "x").expect_err("solution should fail");
+        assert_eq!(
+            format!("{err:#}"),
+            "running registry::tests::first_number: invalid digit found in string"
+        );
+
+        let err = run(1999, 3, 1, "").expect_err("part should be registered twice");
+        assert!(
+            err.to_string()
+                .starts_with("1999 day 3 part 1 is registered twice"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! `#[aoc(year, day, part)]`, which registers a solution with the `registry`
+//! crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{meta::ParseNestedMeta, parse_macro_input, Error, ItemFn, LitInt, Result};
+
+/// Registers a `fn(&str) -> T` as the solution for one part of a puzzle,
+/// where `T` is any `registry::Answer`, like
+/// `#[aoc(year = 2023, day = 5, part = 2)]`.
+#[proc_macro_attribute]
+pub fn aoc(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut key = Key::default();
+    let parser = syn::meta::parser(|meta| key.parse(meta));
+    parse_macro_input!(args with parser);
+
+    let item = parse_macro_input!(item as ItemFn);
+    expand(key, item)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct Key {
+    year: Option<u16>,
+    day: Option<u8>,
+    part: Option<u8>,
+}
+
+impl Key {
+    fn parse(&mut self, meta: ParseNestedMeta) -> Result<()> {
+        let value: LitInt = meta.value()?.parse()?;
+        if meta.path.is_ident("year") {
+            self.year = Some(value.base10_parse()?);
+        } else if meta.path.is_ident("day") {
+            let day = value.base10_parse()?;
+            if !(1..=25).contains(&day) {
+                return Err(Error::new(value.span(), "day must be between 1 and 25"));
+            }
+            self.day = Some(day);
+        } else if meta.path.is_ident("part") {
+            let part = value.base10_parse()?;
+            if !(1..=2).contains(&part) {
+                return Err(Error::new(value.span(), "part must be 1 or 2"));
+            }
+            self.part = Some(part);
+        } else {
+            return Err(meta.error("expected `year`, `day` or `part`"));
+        }
+        Ok(())
+    }
+}
+
+fn expand(key: Key, item: ItemFn) -> Result<proc_macro2::TokenStream> {
+    let (Some(year), Some(day), Some(part)) = (key.year, key.day, key.part) else {
+        return Err(Error::new(
+            Span::call_site(),
+            "expected #[aoc(year = ..., day = ..., part = ...)]",
+        ));
+    };
+
+    if item.sig.inputs.len() != 1 {
+        return Err(Error::new_spanned(
+            &item.sig.inputs,
+            "a solution takes the puzzle input as its only argument",
+        ));
+    }
+
+    let ident = &item.sig.ident;
+
+    Ok(quote! {
+        #item
+
+        ::registry::inventory::submit! {
+            ::registry::Solution::new(
+                #year,
+                #day,
+                #part,
+                concat!(module_path!(), "::", stringify!(#ident)),
+                |input| ::registry::Answer::into_answer(#ident(input)),
+            )
+        }
+    })
+}
@@ -0,0 +1,42 @@
+//! Calorie lists for `size` elves.
+
+use anyhow::{Context, Result};
+use rand::Rng as _;
+
+use crate::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let elves: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            (0..rng.gen_range(1..=15))
+                .map(|_| format!("{}\n", rng.gen_range(1000..=60000)))
+                .collect()
+        })
+        .collect();
+    elves.join("\n")
+}
+
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let mut totals = input
+        .split("\n\n")
+        .map(|elf| elf.lines().map(|l| l.parse::<u64>()).sum())
+        .collect::<Result<Vec<u64>, _>>()
+        .context("parsing calories")?;
+    totals.sort_unstable_by(|a, b| b.cmp(a));
+
+    let most = totals.first().context("no elves")?;
+    let top_three: u64 = totals.iter().take(3).sum();
+    Ok([most.to_string(), top_three.to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_example() -> Result<()> {
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+        assert_eq!(solve(input)?, ["24000", "45000"]);
+        Ok(())
+    }
+}
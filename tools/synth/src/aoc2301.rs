@@ -0,0 +1,69 @@
+//! `size` lines of calibration document, each with letters, digits and the
+//! names of digits run together.
+
+use anyhow::{Context, Result};
+use rand::Rng as _;
+
+use crate::Rng;
+
+const NAMES: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let mut pieces: Vec<String> = (0..rng.gen_range(2..=8))
+                .map(|_| match rng.gen_range(0..4) {
+                    0 => NAMES[rng.gen_range(0..NAMES.len())].to_string(),
+                    1 => rng.gen_range(1..=9).to_string(),
+                    _ => (0..rng.gen_range(1..=4))
+                        .map(|_| char::from(rng.gen_range(b'a'..=b'z')))
+                        .collect(),
+                })
+                .collect();
+            // part one needs a digit on every line
+            let at = rng.gen_range(0..=pieces.len());
+            pieces.insert(at, rng.gen_range(1..=9).to_string());
+            format!("{}\n", pieces.concat())
+        })
+        .collect()
+}
+
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let mut totals = [0, 0];
+    for (idx, line) in input.lines().enumerate() {
+        let digits = |words: bool| {
+            let values: Vec<u32> = (0..line.len())
+                .filter_map(|at| {
+                    let rest = &line[at..];
+                    let digit = rest.chars().next()?.to_digit(10);
+                    let name = || {
+                        let name = NAMES.iter().position(|name| rest.starts_with(name))?;
+                        Some(name as u32 + 1)
+                    };
+                    digit.or_else(|| words.then(name).flatten())
+                })
+                .collect();
+            Some(values.first()? * 10 + values.last()?)
+        };
+        for (total, words) in totals.iter_mut().zip([false, true]) {
+            *total += digits(words).with_context(|| format!("line {}: no digit", idx + 1))?;
+        }
+    }
+    Ok(totals.map(|total| total.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n", ["142", "142"] ; "digits")]
+    #[test_case("two1nine\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen\n", ["209", "198"] ; "names")]
+    fn test_solve_example(input: &str, expected: [&str; 2]) -> Result<()> {
+        assert_eq!(solve(input)?, expected);
+        Ok(())
+    }
+}
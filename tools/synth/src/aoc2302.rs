@@ -0,0 +1,109 @@
+//! Records of `size` games. Most show every colour at least once, but some
+//! never show one of them, which leaves their power at zero.
+
+use anyhow::{bail, Context, Result};
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::Rng;
+
+const COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+/// The cubes in the bag part one asks about, in the order of [`COLOURS`].
+const BAG: [u32; 3] = [12, 13, 14];
+
+/// How often a game never shows one of the colours.
+const MISSING: f64 = 0.2;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (1..=size.max(1))
+        .map(|id| {
+            let mut shown = COLOURS.to_vec();
+            if rng.gen_bool(MISSING) {
+                shown.remove(rng.gen_range(0..shown.len()));
+            }
+            let mut sets: Vec<Vec<(u32, &str)>> = (0..rng.gen_range(1..=6))
+                .map(|_| {
+                    let mut colours = shown.clone();
+                    colours.shuffle(rng);
+                    colours[..rng.gen_range(1..=colours.len())]
+                        .iter()
+                        .map(|&colour| (rng.gen_range(1..=20), colour))
+                        .collect()
+                })
+                .collect();
+            for &colour in &shown {
+                if !sets.iter().flatten().any(|&(_, c)| c == colour) {
+                    let set = rng.gen_range(0..sets.len());
+                    sets[set].push((rng.gen_range(1..=20), colour));
+                }
+            }
+
+            let sets: Vec<String> = sets
+                .iter()
+                .map(|set| {
+                    let cubes: Vec<String> = set.iter().map(|(n, c)| format!("{n} {c}")).collect();
+                    cubes.join(", ")
+                })
+                .collect();
+            format!("Game {id}: {}\n", sets.join("; "))
+        })
+        .collect()
+}
+
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let (mut possible, mut power) = (0, 0);
+    for line in input.lines() {
+        let (game, sets) = line.split_once(": ").context("missing game id")?;
+        let id: u32 = game
+            .strip_prefix("Game ")
+            .context("missing game id")?
+            .parse()?;
+
+        let mut fewest = [0; 3];
+        for cubes in sets.split("; ").flat_map(|set| set.split(", ")) {
+            let (count, colour) = cubes.split_once(' ').context("missing colour")?;
+            let Some(idx) = COLOURS.iter().position(|&c| c == colour) else {
+                bail!("unknown colour {colour:?}")
+            };
+            fewest[idx] = fewest[idx].max(count.parse()?);
+        }
+
+        if fewest.iter().zip(BAG).all(|(&n, max)| n <= max) {
+            possible += id;
+        }
+        power += fewest.iter().product::<u32>();
+    }
+    Ok([possible.to_string(), power.to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn test_solve_example() -> Result<()> {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+        assert_eq!(solve(input)?, ["8", "2286"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_leaves_out_colours() {
+        let input = generate(&mut Rng::seed_from_u64(0), 100);
+        let missing = input
+            .lines()
+            .filter(|game| COLOURS.iter().any(|colour| !game.contains(colour)))
+            .count();
+        assert!(
+            missing > 0 && missing < 100,
+            "{missing} games miss a colour"
+        );
+    }
+}
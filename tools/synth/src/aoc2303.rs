@@ -0,0 +1,111 @@
+//! A `size` by `size` engine schematic of numbers and symbols, with some
+//! numbers clear of every symbol and some gears between two parts.
+
+use anyhow::Result;
+use rand::Rng as _;
+
+use crate::Rng;
+
+const SYMBOLS: &[u8] = b"*#+$/=%@&-";
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(3);
+    let mut grid = vec![vec![b'.'; size]; size];
+    for row in &mut grid {
+        let mut c = 0;
+        while c < size {
+            match rng.gen_range(0..20) {
+                0..=3 => {
+                    let number = rng.gen_range(1..1000).to_string();
+                    let end = (c + number.len()).min(size);
+                    row[c..end].copy_from_slice(&number.as_bytes()[..end - c]);
+                    // leave a gap so the next number doesn't run on from this one
+                    c = end + 1;
+                }
+                4 => {
+                    row[c] = match rng.gen_bool(0.4) {
+                        true => b'*',
+                        false => SYMBOLS[rng.gen_range(0..SYMBOLS.len())],
+                    };
+                    c += 1;
+                }
+                _ => c += 1,
+            }
+        }
+    }
+    grid.iter()
+        .map(|row| format!("{}\n", String::from_utf8_lossy(row)))
+        .collect()
+}
+
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let grid: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+
+    // every number with the cells around it
+    let mut numbers = vec![];
+    for (l, row) in grid.iter().enumerate() {
+        let mut c = 0;
+        while c < row.len() {
+            let end = c + row[c..].iter().take_while(|b| b.is_ascii_digit()).count();
+            if end == c {
+                c += 1;
+                continue;
+            }
+            let value: u64 = std::str::from_utf8(&row[c..end])?.parse()?;
+            let around: Vec<(usize, usize)> = (l.saturating_sub(1)..=l + 1)
+                .flat_map(|l| (c.saturating_sub(1)..=end).map(move |c| (l, c)))
+                .filter(|&(l, c)| grid.get(l).is_some_and(|row| c < row.len()))
+                .collect();
+            numbers.push((value, around));
+            c = end;
+        }
+    }
+
+    let is_symbol = |b: u8| b != b'.' && !b.is_ascii_digit();
+    let parts: u64 = numbers
+        .iter()
+        .filter(|(_, around)| around.iter().any(|&(l, c)| is_symbol(grid[l][c])))
+        .map(|(value, _)| value)
+        .sum();
+
+    let mut ratios = 0;
+    for (l, row) in grid.iter().enumerate() {
+        for (c, &b) in row.iter().enumerate() {
+            if b != b'*' {
+                continue;
+            }
+            let touching: Vec<u64> = numbers
+                .iter()
+                .filter(|(_, around)| around.contains(&(l, c)))
+                .map(|(value, _)| *value)
+                .collect();
+            if let [one, two] = touching[..] {
+                ratios += one * two;
+            }
+        }
+    }
+
+    Ok([parts.to_string(), ratios.to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_example() -> Result<()> {
+        let input = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+        assert_eq!(solve(input)?, ["4361", "467835"]);
+        Ok(())
+    }
+}
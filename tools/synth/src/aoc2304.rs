@@ -0,0 +1,94 @@
+//! A table of `size` scratchcards, none winning copies past the last card.
+
+use anyhow::{Context, Result};
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::Rng;
+
+const WINNING: usize = 10;
+const SCRATCHED: usize = 25;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let width = size.to_string().len();
+    let numbers = |values: &[u32]| {
+        let values: Vec<String> = values.iter().map(|v| format!("{v:>2}")).collect();
+        values.join(" ")
+    };
+
+    // cards come in runs that only win copies of cards in the same run, so
+    // the copies won pile up within a run and start again after it
+    let mut run = 0;
+    (1..=size)
+        .map(|id| {
+            if run == 0 {
+                run = rng.gen_range(10..=25);
+            }
+            run -= 1;
+
+            let mut pool: Vec<u32> = (1..100).collect();
+            pool.shuffle(rng);
+            let (winning, rest) = pool.split_at(WINNING);
+
+            let matches = rng.gen_range(0..=WINNING.min(run).min(size - id));
+            let mut scratched = winning[..matches].to_vec();
+            scratched.extend(&rest[..SCRATCHED - matches]);
+            scratched.shuffle(rng);
+
+            format!(
+                "Card {id:>width$}: {} | {}\n",
+                numbers(winning),
+                numbers(&scratched)
+            )
+        })
+        .collect()
+}
+
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let matches = input
+        .lines()
+        .map(|line| {
+            let (_, numbers) = line.split_once(':').context("missing card id")?;
+            let (winning, scratched) = numbers.split_once('|').context("missing '|'")?;
+            let winning: Vec<&str> = winning.split_whitespace().collect();
+            Ok(scratched
+                .split_whitespace()
+                .filter(|n| winning.contains(n))
+                .count())
+        })
+        .collect::<Result<Vec<usize>>>()?;
+
+    let points: u64 = matches
+        .iter()
+        .filter(|&&m| m > 0)
+        .map(|&m| 1 << (m - 1))
+        .sum();
+
+    let mut copies = vec![1u64; matches.len()];
+    for (idx, &m) in matches.iter().enumerate() {
+        for won in idx + 1..(idx + 1 + m).min(matches.len()) {
+            copies[won] += copies[idx];
+        }
+    }
+    let cards: u64 = copies.iter().sum();
+
+    Ok([points.to_string(), cards.to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_example() -> Result<()> {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+        assert_eq!(solve(input)?, ["13", "30"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+//! An almanac with `size` seed ranges and a few times as many mappers per
+//! map. Values stay small enough for a solution to try every seed in the
+//! ranges one by one.
+
+use std::ops::Range;
+
+use anyhow::{bail, Context, Result};
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::Rng;
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let span = 1000 * size as u64;
+
+    let seeds: Vec<String> = (0..size)
+        .map(|_| {
+            let len = rng.gen_range(1..=500);
+            format!("{} {len}", rng.gen_range(0..span - len))
+        })
+        .collect();
+    let mut almanac = format!("seeds: {}\n", seeds.join(" "));
+
+    for map in MAPS {
+        // cut the values into pieces and lay them out again in another order,
+        // leaving a few pieces out so they map to themselves
+        let mut cuts: Vec<u64> = (0..rng.gen_range(size..=3 * size))
+            .map(|_| rng.gen_range(1..span))
+            .chain([0, span])
+            .collect();
+        cuts.sort_unstable();
+        cuts.dedup();
+        let pieces: Vec<Range<u64>> = cuts.windows(2).map(|w| w[0]..w[1]).collect();
+
+        let mut order: Vec<&Range<u64>> = pieces.iter().collect();
+        order.shuffle(rng);
+        let mut mappers = vec![];
+        let mut dst = 0;
+        for piece in order {
+            if rng.gen_bool(0.9) {
+                mappers.push(format!(
+                    "{dst} {} {}\n",
+                    piece.start,
+                    piece.end - piece.start
+                ));
+            }
+            dst += piece.end - piece.start;
+        }
+        mappers.shuffle(rng);
+
+        almanac.push_str(&format!("\n{map} map:\n{}", mappers.concat()));
+    }
+    almanac
+}
+
+/// One map's mappers as the source range and the offset to add.
+type Map = Vec<(Range<i64>, i64)>;
+
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let mut blocks = input.split("\n\n");
+    let seeds = blocks
+        .next()
+        .and_then(|b| b.strip_prefix("seeds:"))
+        .context("missing seeds")?;
+    let seeds = seeds
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<Vec<i64>, _>>()?;
+
+    let maps = blocks
+        .map(|block| {
+            block
+                .lines()
+                .skip(1)
+                .map(|line| {
+                    let values = line
+                        .split_whitespace()
+                        .map(str::parse)
+                        .collect::<Result<Vec<i64>, _>>()?;
+                    let [dst, src, len] = values[..] else {
+                        bail!("expected 3 values in {line:?}")
+                    };
+                    Ok((src..src + len, dst - src))
+                })
+                .collect::<Result<Map>>()
+        })
+        .collect::<Result<Vec<Map>>>()?;
+
+    if seeds.len() % 2 != 0 {
+        bail!("seeds don't pair up into ranges")
+    }
+    let singles: Vec<Range<i64>> = seeds.iter().map(|&s| s..s + 1).collect();
+    let ranges: Vec<Range<i64>> = seeds.chunks_exact(2).map(|c| c[0]..c[0] + c[1]).collect();
+
+    let closest = |ranges: Vec<Range<i64>>| {
+        let located = maps.iter().fold(ranges, |ranges, map| through(map, ranges));
+        located.iter().map(|r| r.start).min().context("no seeds")
+    };
+    Ok([closest(singles)?.to_string(), closest(ranges)?.to_string()])
+}
+
+/// Sends every range through the map, splitting them where mappers start and
+/// end. Each value takes the first mapper that covers it.
+fn through(map: &Map, mut unmapped: Vec<Range<i64>>) -> Vec<Range<i64>> {
+    let mut mapped = vec![];
+    for (src, offset) in map {
+        let mut missed = vec![];
+        for range in unmapped {
+            let start = range.start.max(src.start);
+            let end = range.end.min(src.end);
+            if start >= end {
+                missed.push(range);
+                continue;
+            }
+            mapped.push(start + offset..end + offset);
+            missed.extend(
+                [range.start..start, end..range.end]
+                    .into_iter()
+                    .filter(|r| !r.is_empty()),
+            );
+        }
+        unmapped = missed;
+    }
+    mapped.extend(unmapped);
+    mapped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_example() -> Result<()> {
+        let input = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+        assert_eq!(solve(input)?, ["35", "46"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+//! `size` different camel card hands, each with a bid.
+
+use std::collections::HashSet;
+
+use anyhow::{bail, Context, Result};
+use rand::Rng as _;
+
+use crate::Rng;
+
+const CARDS: &[u8] = b"23456789TJQKA";
+const WITH_JOKER: &[u8] = b"J23456789TQKA";
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // there are only so many hands to deal
+    let size = size.clamp(1, CARDS.len().pow(5));
+    let mut dealt = HashSet::new();
+    let mut game = String::new();
+    while dealt.len() < size {
+        let hand: String = (0..5)
+            .map(|_| char::from(CARDS[rng.gen_range(0..CARDS.len())]))
+            .collect();
+        if dealt.insert(hand.clone()) {
+            game.push_str(&format!("{hand} {}\n", rng.gen_range(1..=1000)));
+        }
+    }
+    game
+}
+
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let sets = input
+        .lines()
+        .map(|line| {
+            let (hand, bid) = line.split_once(' ').context("missing bid")?;
+            if hand.len() != 5 || !hand.bytes().all(|b| CARDS.contains(&b)) {
+                bail!("invalid hand {hand:?}")
+            }
+            Ok((hand.as_bytes(), bid.parse::<u64>()?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let winnings = |strength: fn(&[u8]) -> (u8, Vec<usize>)| {
+        let mut ranked: Vec<_> = sets
+            .iter()
+            .map(|&(hand, bid)| (strength(hand), bid))
+            .collect();
+        ranked.sort_unstable();
+        let total: u64 = ranked
+            .iter()
+            .zip(1..)
+            .map(|((_, bid), rank)| bid * rank)
+            .sum();
+        total.to_string()
+    };
+
+    Ok([winnings(plain), winnings(joker)])
+}
+
+/// Orders hands by type, then card by card.
+fn plain(hand: &[u8]) -> (u8, Vec<usize>) {
+    (kind(hand), ranks(hand, CARDS))
+}
+
+/// Orders hands by the best type the jokers can stand in for, then card by
+/// card with jokers lowest.
+fn joker(hand: &[u8]) -> (u8, Vec<usize>) {
+    // jokers all standing in for the same card always makes the best hand
+    let best = WITH_JOKER[1..]
+        .iter()
+        .map(|&card| {
+            let hand: Vec<u8> = hand
+                .iter()
+                .map(|&c| if c == b'J' { card } else { c })
+                .collect();
+            kind(&hand)
+        })
+        .max()
+        .unwrap_or_default();
+    (best, ranks(hand, WITH_JOKER))
+}
+
+/// From high card at 0 up to five of a kind at 6.
+fn kind(hand: &[u8]) -> u8 {
+    let mut counts: Vec<usize> = CARDS
+        .iter()
+        .map(|card| hand.iter().filter(|&c| c == card).count())
+        .filter(|&n| n > 0)
+        .collect();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    match counts[..] {
+        [5] => 6,
+        [4, 1] => 5,
+        [3, 2] => 4,
+        [3, ..] => 3,
+        [2, 2, 1] => 2,
+        [2, ..] => 1,
+        _ => 0,
+    }
+}
+
+fn ranks(hand: &[u8], order: &[u8]) -> Vec<usize> {
+    hand.iter()
+        .filter_map(|card| order.iter().position(|c| c == card))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_example() -> Result<()> {
+        let input = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n";
+        assert_eq!(solve(input)?, ["6440", "5905"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+//! A network of roughly `size` nodes, up to 15,000, walked by up to six
+//! ghosts.
+//!
+//! Each ghost follows a loop through its own nodes, `n * m` long for `n`
+//! directions and a prime `m` that differs between ghosts. Node `i` of a loop
+//! leads on to node `i + 1` along direction `i + 1`, so the ghost stays on it,
+//! and the turn it never takes goes anywhere. The last node of each loop ends
+//! in `Z`, and its start, which ends in `A`, leads into the first.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Context, Result};
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::Rng;
+
+const PRIMES: [usize; 16] = [
+    11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71,
+];
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(1, 15_000);
+    let ghosts = rng.gen_range(2..=6);
+    let mut primes = PRIMES;
+    primes.shuffle(rng);
+    let primes = &primes[..ghosts];
+    let count = (size / primes.iter().sum::<usize>()).max(1);
+    let directions: Vec<char> = (0..count)
+        .map(|_| if rng.gen_bool(0.5) { 'L' } else { 'R' })
+        .collect();
+
+    let mut names = names(rng);
+    let mut starts = names_ending(rng, 'A', "AAA", ghosts);
+    let mut ends = names_ending(rng, 'Z', "ZZZ", ghosts);
+
+    let loops: Vec<Vec<String>> = primes
+        .iter()
+        .map(|m| {
+            let mut nodes: Vec<String> = names.drain(..count * m - 1).collect();
+            nodes.push(ends.remove(0));
+            nodes
+        })
+        .collect();
+    let all: Vec<&String> = loops.iter().flatten().collect();
+
+    let node = |rng: &mut Rng, turn: char, name: &str, next: &str| {
+        let other = all[rng.gen_range(0..all.len())];
+        match turn {
+            'L' => format!("{name} = ({next}, {other})\n"),
+            _ => format!("{name} = ({other}, {next})\n"),
+        }
+    };
+    let mut nodes = vec![];
+    for nodes_in_loop in &loops {
+        let start = starts.remove(0);
+        nodes.push(node(rng, directions[0], &start, &nodes_in_loop[0]));
+        for (idx, name) in nodes_in_loop.iter().enumerate() {
+            let next = &nodes_in_loop[(idx + 1) % nodes_in_loop.len()];
+            nodes.push(node(rng, directions[(idx + 1) % count], name, next));
+        }
+    }
+    nodes.shuffle(rng);
+
+    format!(
+        "{}\n\n{}",
+        directions.iter().collect::<String>(),
+        nodes.concat()
+    )
+}
+
+/// Node names neither starting nor ending a walk, in a random order.
+fn names(rng: &mut Rng) -> Vec<String> {
+    let letters = || b'A'..=b'Z';
+    let mut names: Vec<String> = letters()
+        .flat_map(|a| letters().flat_map(move |b| (b'B'..=b'Y').map(move |c| [a, b, c])))
+        .map(|name| String::from_utf8_lossy(&name).into_owned())
+        .collect();
+    names.shuffle(rng);
+    names
+}
+
+/// `count` names ending in `last`, the first being `first`.
+fn names_ending(rng: &mut Rng, last: char, first: &str, count: usize) -> Vec<String> {
+    let mut names = vec![first.to_string()];
+    while names.len() < count {
+        let name: String = (0..2)
+            .map(|_| char::from(rng.gen_range(b'A'..=b'Z')))
+            .chain([last])
+            .collect();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let (directions, nodes) = input.split_once("\n\n").context("missing nodes")?;
+    let directions = directions.as_bytes();
+    let nodes = nodes
+        .lines()
+        .map(|line| {
+            let (name, next) = line.split_once(" = ").context("missing '='")?;
+            let next = next.trim_start_matches('(').trim_end_matches(')');
+            let (left, right) = next.split_once(", ").context("missing ','")?;
+            Ok((name, (left, right)))
+        })
+        .collect::<Result<HashMap<&str, (&str, &str)>>>()?;
+
+    let step = |node: &str, step: usize| {
+        let (left, right) = nodes.get(node).with_context(|| format!("no node {node}"))?;
+        match directions[step % directions.len()] {
+            b'L' => Ok(*left),
+            b'R' => Ok(*right),
+            turn => bail!("invalid direction {:?}", char::from(turn)),
+        }
+    };
+
+    // every walk has repeated itself by the time it has passed through every
+    // node at every direction
+    let limit = nodes.len() * directions.len() + 1;
+
+    let mut node = "AAA";
+    let mut steps = 0;
+    while node != "ZZZ" {
+        if steps == limit {
+            bail!("AAA never reaches ZZZ")
+        }
+        node = step(node, steps)?;
+        steps += 1;
+    }
+
+    let ghosts = nodes
+        .keys()
+        .filter(|n| n.ends_with('A'))
+        .map(|start| {
+            ghost(start, directions.len(), limit, step)
+                .with_context(|| format!("walking from {start}"))
+        })
+        .collect::<Result<Vec<Ghost>>>()?;
+    // each way of picking one end per ghost that they can all be at at once
+    let together = ghosts.iter().fold(vec![(0, 1)], |together, ghost| {
+        together
+            .iter()
+            .flat_map(|&a| {
+                ghost
+                    .ends
+                    .iter()
+                    .filter_map(move |&end| combine(a, (end, ghost.period)))
+            })
+            .collect()
+    });
+    // and the first time after every ghost has settled into its loop
+    let settled = ghosts.iter().map(|g| g.settled).max().unwrap_or_default();
+    let together = together
+        .into_iter()
+        .map(|(t, period)| match t < settled {
+            true => t + (settled - t).div_ceil(period) * period,
+            false => t,
+        })
+        .min()
+        .context("ghosts never all reach an end together")?;
+
+    Ok([steps.to_string(), together.to_string()])
+}
+
+/// Where a ghost's walk ends up: after `settled` steps it loops every
+/// `period` steps, reaching an end only at `ends` steps modulo `period`.
+struct Ghost {
+    settled: u128,
+    period: u128,
+    ends: Vec<u128>,
+}
+
+fn ghost<'a>(
+    start: &'a str,
+    directions: usize,
+    limit: usize,
+    step: impl Fn(&'a str, usize) -> Result<&'a str>,
+) -> Result<Ghost> {
+    let mut seen = HashMap::new();
+    let mut ends = vec![];
+    let mut node = start;
+    for steps in 0..=limit {
+        if let Some(&first) = seen.get(&(node, steps % directions)) {
+            if ends.iter().any(|&end| end < first) {
+                bail!("reached an end before settling into a loop")
+            }
+            return Ok(Ghost {
+                settled: first as u128,
+                period: (steps - first) as u128,
+                ends: ends.into_iter().map(|end| end as u128).collect(),
+            });
+        }
+        seen.insert((node, steps % directions), steps);
+        if node.ends_with('Z') {
+            ends.push(steps);
+        }
+        node = step(node, steps)?;
+    }
+    bail!("walk never repeats")
+}
+
+/// Combines `t = a mod m` and `t = b mod n` into one congruence modulo the
+/// lcm of `m` and `n`, if any `t` satisfies both.
+fn combine((a, m): (u128, u128), (b, n): (u128, u128)) -> Option<(u128, u128)> {
+    let (g, x, _) = extended_gcd(m as i128, n as i128);
+    let diff = b as i128 - a as i128;
+    if diff % g != 0 {
+        return None;
+    }
+    let lcm = m / g as u128 * n;
+    // t = a + m * k, where m * k = b - a modulo n
+    let k = (diff / g * x).rem_euclid(n as i128 / g);
+    let t = (a as i128 + m as i128 * k).rem_euclid(lcm as i128);
+    Some((t as u128, lcm))
+}
+
+/// The gcd of `a` and `b` along with `x` and `y` where `a * x + b * y` is the
+/// gcd.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    match b {
+        0 => (a, 1, 0),
+        _ => {
+            let (g, x, y) = extended_gcd(b, a % b);
+            (g, y, x - a / b * y)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)
+", ["2", "2"] ; "straight")]
+    #[test_case("LR
+
+AAA = (11B, XXX)
+11B = (XXX, ZZZ)
+ZZZ = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+", ["2", "6"] ; "ghosts")]
+    fn test_solve_example(input: &str, expected: [&str; 2]) -> Result<()> {
+        assert_eq!(solve(input)?, expected);
+        Ok(())
+    }
+
+    #[test_case((2, 3), (3, 5), Some((8, 15)) ; "coprime")]
+    #[test_case((2, 4), (4, 6), Some((10, 12)) ; "shared factor")]
+    #[test_case((1, 4), (2, 6), None ; "no solution")]
+    fn test_combine(a: (u128, u128), b: (u128, u128), expected: Option<(u128, u128)>) {
+        assert_eq!(combine(a, b), expected);
+    }
+}
@@ -0,0 +1,75 @@
+//! A report of `size` histories, each 21 values of a polynomial of degree at
+//! most 10.
+
+use anyhow::{bail, Result};
+use rand::Rng as _;
+
+use crate::Rng;
+
+const LENGTH: usize = 21;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            // start from a row of constant differences and add it up into
+            // each row above it in turn
+            let degree = rng.gen_range(0..=10);
+            let mut values = vec![rng.gen_range(-9i64..=9); LENGTH];
+            for _ in 0..degree {
+                let mut value = rng.gen_range(-20..=20);
+                values = values
+                    .iter()
+                    .map(|diff| {
+                        let current = value;
+                        value += diff;
+                        current
+                    })
+                    .collect();
+            }
+            let values: Vec<String> = values.iter().map(i64::to_string).collect();
+            format!("{}\n", values.join(" "))
+        })
+        .collect()
+}
+
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let (mut next, mut previous) = (0, 0);
+    for line in input.lines() {
+        let values = line
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<i128>, _>>()?;
+        if values.is_empty() {
+            bail!("empty history")
+        }
+        next += extrapolate(values.iter().copied());
+        previous += extrapolate(values.iter().rev().copied());
+    }
+    Ok([next.to_string(), previous.to_string()])
+}
+
+/// The value after `values`, taking them as a polynomial of degree less than
+/// their count: `sum (-1)^(n - 1 - i) * C(n, i) * values[i]`.
+fn extrapolate(values: impl ExactSizeIterator<Item = i128>) -> i128 {
+    let n = values.len();
+    let mut choose = 1; // C(n, i)
+    let mut next = 0;
+    for (i, value) in values.enumerate() {
+        let sign = if (n - 1 - i).is_multiple_of(2) { 1 } else { -1 };
+        next += sign * choose * value;
+        choose = choose * (n - i) as i128 / (i + 1) as i128;
+    }
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_example() -> Result<()> {
+        let input = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n";
+        assert_eq!(solve(input)?, ["114", "2"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,285 @@
+//! A `size` by `size` field of pipes with one loop through it, among pipes
+//! that go nowhere.
+//!
+//! The loop is the outline of a shape grown a cell at a time between the
+//! tiles, only ever adding cells that keep it free of holes and of cells that
+//! touch only at a corner, so its outline never crosses itself.
+
+use anyhow::{bail, Context, Result};
+use rand::Rng as _;
+
+use crate::Rng;
+
+const NORTH: u8 = 1;
+const SOUTH: u8 = 2;
+const WEST: u8 = 4;
+const EAST: u8 = 8;
+
+const PIPES: [(char, u8); 6] = [
+    ('|', NORTH | SOUTH),
+    ('-', WEST | EAST),
+    ('L', NORTH | EAST),
+    ('J', NORTH | WEST),
+    ('7', SOUTH | WEST),
+    ('F', SOUTH | EAST),
+];
+
+/// How often the shape fills in a cell beside more than one it already has.
+const WIDEN: f64 = 0.02;
+
+/// Neighbouring cells in order around a cell, starting north and going
+/// clockwise.
+const AROUND: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(3);
+    let shape = grow(rng, size - 1);
+
+    // the directions each tile joins along the outline
+    let mut joins = vec![vec![0u8; size]; size];
+    let filled = |r: isize, c: isize| {
+        let (Ok(r), Ok(c)) = (usize::try_from(r), usize::try_from(c)) else {
+            return false;
+        };
+        shape
+            .get(r)
+            .and_then(|row| row.get(c))
+            .copied()
+            .unwrap_or(false)
+    };
+    for (r, row) in shape.iter().enumerate() {
+        for (c, _) in row.iter().enumerate().filter(|(_, &filled)| filled) {
+            let (ri, ci) = (r as isize, c as isize);
+            if !filled(ri - 1, ci) {
+                joins[r][c] |= EAST;
+                joins[r][c + 1] |= WEST;
+            }
+            if !filled(ri + 1, ci) {
+                joins[r + 1][c] |= EAST;
+                joins[r + 1][c + 1] |= WEST;
+            }
+            if !filled(ri, ci - 1) {
+                joins[r][c] |= SOUTH;
+                joins[r + 1][c] |= NORTH;
+            }
+            if !filled(ri, ci + 1) {
+                joins[r][c + 1] |= SOUTH;
+                joins[r + 1][c + 1] |= NORTH;
+            }
+        }
+    }
+
+    let mut tiles: Vec<Vec<char>> = joins
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&joins| match PIPES.iter().find(|(_, j)| *j == joins) {
+                    Some(&(pipe, _)) => pipe,
+                    None if rng.gen_bool(0.35) => '.',
+                    None => PIPES[rng.gen_range(0..PIPES.len())].0,
+                })
+                .collect()
+        })
+        .collect();
+
+    let outline: Vec<(usize, usize)> = (0..size)
+        .flat_map(|r| (0..size).map(move |c| (r, c)))
+        .filter(|&(r, c)| joins[r][c] != 0)
+        .collect();
+    let (r, c) = outline[rng.gen_range(0..outline.len())];
+    tiles[r][c] = 'S';
+
+    // pipes off the loop mustn't look like they join the start
+    let beside = [(-1, 0, SOUTH), (1, 0, NORTH), (0, -1, EAST), (0, 1, WEST)];
+    for (dr, dc, towards) in beside {
+        let (Some(r), Some(c)) = (r.checked_add_signed(dr), c.checked_add_signed(dc)) else {
+            continue;
+        };
+        if r < size && c < size && joins[r][c] == 0 && pipe_joins(tiles[r][c]) & towards != 0 {
+            tiles[r][c] = '.';
+        }
+    }
+
+    tiles
+        .iter()
+        .map(|row| format!("{}\n", row.iter().collect::<String>()))
+        .collect()
+}
+
+/// Grows a shape over a `cells` by `cells` grid from a single cell in the
+/// middle, beside a random cell it already has each time, until it covers two
+/// fifths of the grid or stops finding room.
+fn grow(rng: &mut Rng, cells: usize) -> Vec<Vec<bool>> {
+    let mut shape = vec![vec![false; cells]; cells];
+    let middle = (cells / 2, cells / 2);
+    shape[middle.0][middle.1] = true;
+
+    let target = cells * cells * 2 / 5;
+    let mut grown = vec![middle];
+    for _ in 0..target * 100 {
+        if grown.len() >= target {
+            break;
+        }
+        let (r, c) = grown[rng.gen_range(0..grown.len())];
+        let (dr, dc) = AROUND[rng.gen_range(0..4) * 2];
+        let (Some(r), Some(c)) = (r.checked_add_signed(dr), c.checked_add_signed(dc)) else {
+            continue;
+        };
+        if r >= cells || c >= cells || shape[r][c] || !can_fill(&shape, r, c) {
+            continue;
+        }
+        // mostly grow thin corridors, so the loop winds about like a real one
+        let sides = around(&shape, r, c)
+            .iter()
+            .step_by(2)
+            .filter(|&&s| s)
+            .count();
+        if sides == 1 || rng.gen_bool(WIDEN) {
+            shape[r][c] = true;
+            grown.push((r, c));
+        }
+    }
+    shape
+}
+
+/// Whether filling the empty cell keeps the shape's outline a single loop:
+/// its filled neighbours have to run together in one unbroken arc around it,
+/// and no filled corner can touch it without a filled side next to it.
+fn can_fill(shape: &[Vec<bool>], r: usize, c: usize) -> bool {
+    let ring = around(shape, r, c);
+
+    let lone_corner = (1..8)
+        .step_by(2)
+        .any(|corner| ring[corner] && !ring[corner - 1] && !ring[(corner + 1) % 8]);
+    let arcs = (0..8).filter(|&i| ring[i] && !ring[(i + 7) % 8]).count();
+    !lone_corner && arcs == 1
+}
+
+/// Which of the cells around this one are filled, in the order of
+/// [`AROUND`].
+fn around(shape: &[Vec<bool>], r: usize, c: usize) -> [bool; 8] {
+    AROUND.map(|(dr, dc)| {
+        let (Some(r), Some(c)) = (r.checked_add_signed(dr), c.checked_add_signed(dc)) else {
+            return false;
+        };
+        shape
+            .get(r)
+            .and_then(|row| row.get(c))
+            .copied()
+            .unwrap_or(false)
+    })
+}
+
+fn pipe_joins(tile: char) -> u8 {
+    PIPES
+        .iter()
+        .find(|(pipe, _)| *pipe == tile)
+        .map_or(0, |&(_, joins)| joins)
+}
+
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let tiles: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+    let tile = |(r, c): (isize, isize)| {
+        let (Ok(r), Ok(c)) = (usize::try_from(r), usize::try_from(c)) else {
+            return '.';
+        };
+        tiles
+            .get(r)
+            .and_then(|row| row.get(c))
+            .copied()
+            .unwrap_or('.')
+    };
+    let step = |(r, c): (isize, isize), direction: u8| match direction {
+        NORTH => (r - 1, c),
+        SOUTH => (r + 1, c),
+        WEST => (r, c - 1),
+        _ => (r, c + 1),
+    };
+    let opposite = |direction: u8| match direction {
+        NORTH => SOUTH,
+        SOUTH => NORTH,
+        WEST => EAST,
+        _ => WEST,
+    };
+
+    let start = tiles
+        .iter()
+        .enumerate()
+        .find_map(|(r, row)| Some((r as isize, row.iter().position(|&t| t == 'S')? as isize)))
+        .context("no start")?;
+    let joined: Vec<u8> = [NORTH, SOUTH, WEST, EAST]
+        .into_iter()
+        .filter(|&d| pipe_joins(tile(step(start, d))) & opposite(d) != 0)
+        .collect();
+    let [mut heading, _] = joined[..] else {
+        bail!("start joins {} pipes", joined.len())
+    };
+
+    // walk the loop, adding up twice the area it encloses as it goes
+    let mut at = start;
+    let mut length = 0;
+    let mut area = 0;
+    loop {
+        let next = step(at, heading);
+        area += at.0 * next.1 - next.0 * at.1;
+        length += 1;
+        at = next;
+        if at == start {
+            break;
+        }
+        let came_from = opposite(heading);
+        heading = pipe_joins(tile(at)) & !came_from;
+        if heading.count_ones() != 1 {
+            bail!("loop breaks at {at:?}");
+        }
+    }
+
+    // Pick's theorem gives the tiles inside from the area and the tiles on
+    // the loop
+    let area = area.abs() / 2;
+    let inside = area - length / 2 + 1;
+    Ok([(length / 2).to_string(), inside.to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("7-F7-\n.FJ|7\nSJLL7\n|F--J\nLJ.LJ\n", ["8", "1"] ; "winding")]
+    #[test_case("...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........
+", ["23", "4"] ; "enclosed")]
+    #[test_case(".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...
+", ["70", "8"] ; "larger")]
+    fn test_solve_example(input: &str, expected: [&str; 2]) -> Result<()> {
+        assert_eq!(solve(input)?, expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+//! Synthetic puzzle inputs for each day, generated from a seed, along with
+//! the answers worked out by a reference solver that shares no code with the
+//! day's own solution.
+//!
+//! Real inputs can't be shared, so these stand in for them anywhere without a
+//! session, like CI.
+
+use anyhow::{Context, Result};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+pub mod aoc2201;
+pub mod aoc2301;
+pub mod aoc2302;
+pub mod aoc2303;
+pub mod aoc2304;
+pub mod aoc2305;
+pub mod aoc2306;
+pub mod aoc2307;
+pub mod aoc2308;
+pub mod aoc2309;
+pub mod aoc2310;
+
+/// The random source every generator draws from. ChaCha gives the same
+/// stream for a seed on every platform and `rand` release.
+pub type Rng = ChaCha8Rng;
+
+/// A generated input with the answer to each part.
+#[derive(Debug)]
+pub struct Puzzle {
+    pub input: String,
+    pub answers: [String; 2],
+}
+
+/// One day's generator and reference solver.
+#[derive(Debug)]
+pub struct Synth {
+    pub year: u16,
+    pub day: u8,
+    /// The size of a realistic input. What it counts depends on the day.
+    pub size: usize,
+    generate: fn(&mut Rng, usize) -> String,
+    solve: fn(&str) -> Result<[String; 2]>,
+}
+
+impl Synth {
+    const fn new(
+        year: u16,
+        day: u8,
+        size: usize,
+        generate: fn(&mut Rng, usize) -> String,
+        solve: fn(&str) -> Result<[String; 2]>,
+    ) -> Synth {
+        Synth {
+            year,
+            day,
+            size,
+            generate,
+            solve,
+        }
+    }
+
+    pub fn generate(&self, seed: u64, size: usize) -> Result<Puzzle> {
+        let mut rng = Rng::seed_from_u64(seed);
+        let input = (self.generate)(&mut rng, size);
+        let answers = self.solve(&input)?;
+        Ok(Puzzle { input, answers })
+    }
+
+    pub fn solve(&self, input: &str) -> Result<[String; 2]> {
+        (self.solve)(input)
+            .with_context(|| format!("solving {} day {} with the reference", self.year, self.day))
+    }
+}
+
+pub const DAYS: &[Synth] = &[
+    Synth::new(2022, 1, 250, aoc2201::generate, aoc2201::solve),
+    Synth::new(2023, 1, 1000, aoc2301::generate, aoc2301::solve),
+    Synth::new(2023, 2, 100, aoc2302::generate, aoc2302::solve),
+    Synth::new(2023, 3, 140, aoc2303::generate, aoc2303::solve),
+    Synth::new(2023, 4, 200, aoc2304::generate, aoc2304::solve),
+    Synth::new(2023, 5, 10, aoc2305::generate, aoc2305::solve),
+    Synth::new(2023, 6, 4, aoc2306::generate, aoc2306::solve),
+    Synth::new(2023, 7, 1000, aoc2307::generate, aoc2307::solve),
+    Synth::new(2023, 8, 750, aoc2308::generate, aoc2308::solve),
+    Synth::new(2023, 9, 200, aoc2309::generate, aoc2309::solve),
+    Synth::new(2023, 10, 140, aoc2310::generate, aoc2310::solve),
+];
+
+pub fn find(year: u16, day: u8) -> Option<&'static Synth> {
+    DAYS.iter().find(|s| (s.year, s.day) == (year, day))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use test_case::test_case;
+
+    use super::*;
+
+    // link every day so its solutions are registered
+    use ::aoc2201 as _;
+    use ::aoc2301 as _;
+    use ::aoc2302 as _;
+    use ::aoc2303 as _;
+    use ::aoc2304 as _;
+    use ::aoc2305 as _;
+    use ::aoc2306 as _;
+    use ::aoc2307 as _;
+    use ::aoc2308 as _;
+    use ::aoc2309 as _;
+    use ::aoc2310 as _;
+
+    #[test]
+    fn test_generate_is_seeded() -> Result<()> {
+        for synth in DAYS {
+            let one = synth.generate(7, synth.size)?;
+            let two = synth.generate(7, synth.size)?;
+            assert_eq!(one.input, two.input, "{} day {}", synth.year, synth.day);
+        }
+        Ok(())
+    }
+
+    #[test_case(2022, 1, 1)]
+    #[test_case(2022, 1, 2)]
+    #[test_case(2023, 1, 1)]
+    #[test_case(2023, 1, 2)]
+    #[test_case(2023, 2, 1)]
+    #[test_case(2023, 2, 2)]
+    #[test_case(2023, 3, 1)]
+    #[test_case(2023, 3, 2)]
+    #[test_case(2023, 4, 1)]
+    #[test_case(2023, 4, 2)]
+    #[test_case(2023, 5, 1)]
+    #[test_case(2023, 5, 2)]
+    #[test_case(2023, 6, 1)]
+    #[test_case(2023, 6, 2)]
+    #[test_case(2023, 7, 1)]
+    #[test_case(2023, 7, 2)]
+    #[test_case(2023, 8, 1)]
+    #[test_case(2023, 8, 2)]
+    #[test_case(2023, 9, 1)]
+    #[test_case(2023, 9, 2)]
+    #[test_case(2023, 10, 1)]
+    #[test_case(2023, 10, 2)]
+    fn test_solution_matches_reference(year: u16, day: u8, part: u8) -> Result<()> {
+        let synth = find(year, day).context("no generator")?;
+        for seed in 0..4 {
+            let puzzle = synth.generate(seed, synth.size)?;
+            let answer = registry::run(year, day, part, &puzzle.input)?;
+            assert_eq!(answer, puzzle.answers[usize::from(part) - 1], "seed {seed}");
+        }
+        Ok(())
+    }
+}